@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use bevy_pancam::{PanCam, PanCamFitEntities, PanCamFlyTo, PanCamPlugin};
+use rand::random;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, PanCamPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (fly_to_random_square, fit_all_squares))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        PanCam {
+            min_x: -1000.,
+            max_x: 1000.,
+            min_y: -1000.,
+            max_y: 1000.,
+            ..default()
+        },
+    ));
+
+    let n = 20;
+    let spacing = 50.;
+    let offset = spacing * n as f32 / 2.;
+    let custom_size = Some(Vec2::new(spacing, spacing));
+    for x in 0..n {
+        for y in 0..n {
+            let x = x as f32 * spacing - offset;
+            let y = y as f32 * spacing - offset;
+            let color = Color::hsl(240., random::<f32>() * 0.3, random::<f32>() * 0.3);
+            commands.spawn((
+                Sprite {
+                    color,
+                    custom_size,
+                    ..default()
+                },
+                Transform::from_xyz(x, y, 0.),
+            ));
+        }
+    }
+}
+
+fn fly_to_random_square(
+    keys: Res<ButtonInput<KeyCode>>,
+    cameras: Query<Entity, With<PanCam>>,
+    squares: Query<&Transform, With<Sprite>>,
+    mut commands: Commands,
+) {
+    // Space = Fly to a random square, grab the camera to cancel
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    let Some(square) = squares
+        .iter()
+        .nth(random::<u32>() as usize % squares.iter().len())
+    else {
+        return;
+    };
+
+    for entity in &cameras {
+        commands.trigger(PanCamFlyTo {
+            entity,
+            translation: square.translation.truncate(),
+            scale: Some(0.25 + random::<f32>() * 2.),
+            duration: 1.,
+            easing: EaseFunction::CubicInOut,
+        });
+    }
+}
+
+fn fit_all_squares(
+    keys: Res<ButtonInput<KeyCode>>,
+    cameras: Query<Entity, With<PanCam>>,
+    squares: Query<Entity, With<Sprite>>,
+    mut commands: Commands,
+) {
+    // F = Zoom to fit all squares
+    if !keys.just_pressed(KeyCode::KeyF) {
+        return;
+    }
+
+    for entity in &cameras {
+        commands.trigger(PanCamFitEntities {
+            entity,
+            entities: squares.iter().collect(),
+            padding: 20.,
+            duration: 0.5,
+        });
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use bevy_pancam::{PanCam, PanCamFollow, PanCamPlugin};
+use rand::random;
+
+#[derive(Component)]
+struct Player;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, PanCamPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_player)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let player = commands
+        .spawn((
+            Player,
+            Sprite {
+                color: Color::WHITE,
+                custom_size: Some(Vec2::splat(30.)),
+                ..default()
+            },
+            Transform::from_xyz(0., 0., 1.),
+        ))
+        .id();
+
+    commands.spawn((
+        Camera2d,
+        PanCam::default(),
+        // drag the camera to look around, it resumes following the player
+        // two seconds later
+        PanCamFollow {
+            dead_zone: Vec2::new(200., 100.),
+            lookahead: 0.5,
+            ..PanCamFollow::new(player)
+        },
+    ));
+
+    let n = 20;
+    let spacing = 50.;
+    let offset = spacing * n as f32 / 2.;
+    let custom_size = Some(Vec2::new(spacing, spacing));
+    for x in 0..n {
+        for y in 0..n {
+            let x = x as f32 * spacing - offset;
+            let y = y as f32 * spacing - offset;
+            let color = Color::hsl(240., random::<f32>() * 0.3, random::<f32>() * 0.3);
+            commands.spawn((
+                Sprite {
+                    color,
+                    custom_size,
+                    ..default()
+                },
+                Transform::from_xyz(x, y, 0.),
+            ));
+        }
+    }
+}
+
+fn move_player(mut player: Query<&mut Transform, With<Player>>, time: Res<Time>) {
+    // wander around in a figure eight
+    let t = time.elapsed_secs() * 0.5;
+    for mut transform in &mut player {
+        transform.translation.x = t.sin() * 400.;
+        transform.translation.y = (2. * t).sin() * 200.;
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_pancam::{ElasticBounds, Momentum, PanCam, PanCamPlugin};
+use rand::random;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, PanCamPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        PanCam {
+            // keep gliding after the mouse button is released, slowing down gradually
+            momentum: Some(Momentum {
+                friction: 4.,
+                ..default()
+            }),
+            // allow dragging a bit past the bounds, springing back on release
+            elastic_bounds: Some(ElasticBounds::default()),
+            min_x: -1000.,
+            max_x: 1000.,
+            min_y: -1000.,
+            max_y: 1000.,
+            ..default()
+        },
+    ));
+
+    let n = 20;
+    let spacing = 50.;
+    let offset = spacing * n as f32 / 2.;
+    let custom_size = Some(Vec2::new(spacing, spacing));
+    for x in 0..n {
+        for y in 0..n {
+            let x = x as f32 * spacing - offset;
+            let y = y as f32 * spacing - offset;
+            let color = Color::hsl(240., random::<f32>() * 0.3, random::<f32>() * 0.3);
+            commands.spawn((
+                Sprite {
+                    color,
+                    custom_size,
+                    ..default()
+                },
+                Transform::from_xyz(x, y, 0.),
+            ));
+        }
+    }
+}
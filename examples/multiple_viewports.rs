@@ -3,7 +3,7 @@ use bevy::{
     prelude::*,
     window::WindowResized,
 };
-use bevy_pancam::{PanCam, PanCamClampBounds, PanCamPlugin};
+use bevy_pancam::{PanCam, PanCamPlugin};
 use rand::random;
 
 #[derive(Component)]
@@ -97,11 +97,10 @@ fn reset_viewports(
     windows: Query<&Window>,
     mut resize_events: MessageReader<WindowResized>,
     mut left_camera: Query<&mut Camera, (With<LeftCamera>, Without<RightCamera>)>,
-    mut right_camera: Query<(Entity, &mut Camera), (Without<LeftCamera>, With<RightCamera>)>,
-    mut commands: Commands,
+    mut right_camera: Query<&mut Camera, (Without<LeftCamera>, With<RightCamera>)>,
 ) -> Result {
     let mut l = left_camera.single_mut()?;
-    let (entity, mut r) = right_camera.single_mut()?;
+    let mut r = right_camera.single_mut()?;
     for resize_event in resize_events.read() {
         let window = windows.get(resize_event.window)?;
         let size = window.physical_size();
@@ -121,9 +120,7 @@ fn reset_viewports(
             ..default()
         });
 
-        // for this kind of thing to work properly, we must manually trigger bevy_pancam to clamp
-        // the bounds, since it only does this automatically when it's the one to move them.
-        commands.trigger(PanCamClampBounds { entity });
+        // bevy_pancam notices the new viewport and keeps the camera within its bounds
     }
 
     Ok(())
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_pancam::{ConvexPolygonBounds, PanCam, PanCamPlugin, ShapeBounds};
+use rand::random;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, PanCamPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let radius = 600.;
+    let hexagon = (0..6).map(|i| Vec2::from_angle(i as f32 * std::f32::consts::FRAC_PI_3) * radius);
+
+    commands.spawn((
+        Camera2d,
+        PanCam {
+            // keep the whole view inside the hexagon. use `ShapeBounds::center`
+            // to only keep the middle of the view inside it
+            shape_bounds: Some(ShapeBounds::new(ConvexPolygonBounds::new(hexagon))),
+            ..default()
+        },
+    ));
+
+    let n = 24;
+    let spacing = 50.;
+    let offset = spacing * n as f32 / 2.;
+    let custom_size = Some(Vec2::new(spacing, spacing));
+    for x in 0..n {
+        for y in 0..n {
+            let x = x as f32 * spacing - offset;
+            let y = y as f32 * spacing - offset;
+            let color = Color::hsl(240., random::<f32>() * 0.3, random::<f32>() * 0.3);
+            commands.spawn((
+                Sprite {
+                    color,
+                    custom_size,
+                    ..default()
+                },
+                Transform::from_xyz(x, y, 0.),
+            ));
+        }
+    }
+}
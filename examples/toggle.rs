@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use bevy_pancam::{PanCam, PanCamPlugin};
+use bevy_pancam::{EdgeScroll, GamepadControls, PanCam, PanCamPlugin, ScrollBehavior};
 use rand::random;
 
 fn main() {
@@ -47,4 +47,40 @@ fn toggle_key(mut query: Query<&mut PanCam>, keys: Res<ButtonInput<KeyCode>>) {
             pancam.zoom_to_cursor = !pancam.zoom_to_cursor;
         }
     }
+    // Z = Toggle Zoom Smoothing
+    if keys.just_pressed(KeyCode::KeyZ) {
+        for mut pancam in &mut query {
+            pancam.zoom_smoothing = match pancam.zoom_smoothing {
+                Some(_) => None,
+                None => Some(10.),
+            };
+        }
+    }
+    // E = Toggle Edge Scrolling
+    if keys.just_pressed(KeyCode::KeyE) {
+        for mut pancam in &mut query {
+            pancam.edge_scroll = match pancam.edge_scroll {
+                Some(_) => None,
+                None => Some(EdgeScroll::default()),
+            };
+        }
+    }
+    // P = Toggle Trackpad Scroll Panning
+    if keys.just_pressed(KeyCode::KeyP) {
+        for mut pancam in &mut query {
+            pancam.scroll_behavior = match pancam.scroll_behavior {
+                ScrollBehavior::Zoom => ScrollBehavior::PixelScrollPans,
+                ScrollBehavior::PixelScrollPans => ScrollBehavior::Zoom,
+            };
+        }
+    }
+    // G = Toggle Gamepad Controls
+    if keys.just_pressed(KeyCode::KeyG) {
+        for mut pancam in &mut query {
+            pancam.gamepad = match pancam.gamepad {
+                Some(_) => None,
+                None => Some(GamepadControls::default()),
+            };
+        }
+    }
 }
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_pancam::{PanCam, PanCamPlugin, PanCamWrapped};
+use rand::random;
+
+const WORLD_WIDTH: f32 = 1000.;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, PanCamPlugin))
+        .add_systems(Startup, setup)
+        .add_observer(log_wraps)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        PanCam {
+            // panning past the east edge continues from the west edge. zooming
+            // out stops before the world would be shown twice
+            wrap_x: true,
+            min_x: -WORLD_WIDTH / 2.,
+            max_x: WORLD_WIDTH / 2.,
+            min_y: -500.,
+            max_y: 500.,
+            ..default()
+        },
+    ));
+
+    let n = 20;
+    let spacing = 50.;
+    let offset = spacing * n as f32 / 2.;
+    let custom_size = Some(Vec2::new(spacing, spacing));
+    for x in 0..n {
+        for y in 0..n {
+            let x = x as f32 * spacing - offset;
+            let y = y as f32 * spacing - offset;
+            let color = Color::hsl(240., random::<f32>() * 0.3, random::<f32>() * 0.3);
+            // draw a copy of the world on each side, so the view is always
+            // covered around the seam
+            for copy in [-WORLD_WIDTH, 0., WORLD_WIDTH] {
+                commands.spawn((
+                    Sprite {
+                        color,
+                        custom_size,
+                        ..default()
+                    },
+                    Transform::from_xyz(x + copy, y, 0.),
+                ));
+            }
+        }
+    }
+}
+
+fn log_wraps(wrapped: On<PanCamWrapped>) {
+    info!("camera wrapped around by {}", wrapped.offset);
+}
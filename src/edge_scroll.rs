@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+/// Settings for edge scrolling, where the camera moves when the cursor is
+/// close to the edge of its viewport
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct EdgeScroll {
+    /// How close to the viewport edge the cursor has to be to start
+    /// scrolling, in logical pixels
+    pub margin: f32,
+    /// Scrolling speed with the cursor right at the viewport edge, in logical
+    /// pixels per second
+    pub speed: f32,
+    /// Shapes how the speed ramps up as the cursor approaches the edge
+    ///
+    /// `1.` increases the speed linearly, higher values keep the camera slow
+    /// until the cursor is close to the edge, and `0.` scrolls at full speed
+    /// anywhere within the margin.
+    pub speed_curve: f32,
+    /// Only scroll while the window has focus
+    pub require_focus: bool,
+}
+
+impl Default for EdgeScroll {
+    fn default() -> Self {
+        Self {
+            margin: 20.,
+            speed: 600.,
+            speed_curve: 1.,
+            require_focus: true,
+        }
+    }
+}
+
+impl EdgeScroll {
+    /// Returns the scroll direction for a logical cursor position, scaled by
+    /// how far into the margin the cursor is
+    fn direction(&self, cursor_pos: Vec2, viewport: Rect) -> Vec2 {
+        if !viewport.contains(cursor_pos) || self.margin <= 0. {
+            return Vec2::ZERO;
+        }
+
+        let strength = |distance_to_edge: f32| {
+            let depth = (1. - distance_to_edge / self.margin).clamp(0., 1.);
+            if depth > 0. {
+                depth.powf(self.speed_curve)
+            } else {
+                0.
+            }
+        };
+
+        // Window coordinates grow downwards, world coordinates upwards
+        vec2(
+            strength(viewport.max.x - cursor_pos.x) - strength(cursor_pos.x - viewport.min.x),
+            strength(cursor_pos.y - viewport.min.y) - strength(viewport.max.y - cursor_pos.y),
+        )
+    }
+
+    /// Returns how far to scroll during a frame, in logical pixels with y
+    /// growing upwards, for a logical cursor position in a viewport
+    pub(crate) fn scroll(
+        &self,
+        cursor_pos: Vec2,
+        viewport: Rect,
+        window_focused: bool,
+        delta_secs: f32,
+    ) -> Vec2 {
+        if self.require_focus && !window_focused {
+            return Vec2::ZERO;
+        }
+        delta_secs * self.direction(cursor_pos, viewport) * self.speed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_scroll_speeds_up_towards_the_edge() {
+        let edge_scroll = EdgeScroll::default();
+        let viewport = Rect::new(0., 0., 200., 100.);
+        assert_eq!(edge_scroll.direction(vec2(100., 50.), viewport), Vec2::ZERO);
+        assert_eq!(
+            edge_scroll.direction(vec2(190., 50.), viewport),
+            vec2(0.5, 0.)
+        );
+        assert_eq!(edge_scroll.direction(vec2(0., 0.), viewport), vec2(-1., 1.));
+        assert_eq!(edge_scroll.direction(vec2(250., 50.), viewport), Vec2::ZERO);
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::PanCamSystems;
+use crate::PanCamInputSystems;
 
 #[derive(Resource, Deref, DerefMut, PartialEq, Eq, Default)]
 struct EguiWantsFocus(bool);
@@ -13,7 +13,7 @@ impl Plugin for EguiPanCamPlugin {
             .add_systems(PostUpdate, check_egui_wants_focus)
             .configure_sets(
                 Update,
-                PanCamSystems.run_if(resource_equals(EguiWantsFocus(false))),
+                PanCamInputSystems.run_if(resource_equals(EguiWantsFocus(false))),
             );
     }
 }
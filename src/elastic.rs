@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+
+use crate::{PanCam, clamp_to_safe_zone};
+
+/// Settings for elastic camera bounds, where the camera can be dragged past the
+/// bounds with increasing resistance and springs back once released
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ElasticBounds {
+    /// How far past the bounds the camera can be dragged, in logical pixels
+    ///
+    /// The resistance increases the further the camera is dragged, so this
+    /// distance is only approached, never reached.
+    pub max_overscroll: f32,
+    /// How quickly the camera springs back inside the bounds after being
+    /// released, as an exponential decay rate per second
+    pub stiffness: f32,
+}
+
+impl Default for ElasticBounds {
+    fn default() -> Self {
+        Self {
+            max_overscroll: 100.,
+            stiffness: 12.,
+        }
+    }
+}
+
+impl ElasticBounds {
+    /// Returns where dragging a camera by `delta` moves it, rubber banding
+    /// the part of the drag past the bounds
+    ///
+    /// `unresisted_pos` tracks where the drag would have taken the camera
+    /// without resistance.
+    pub(crate) fn drag(
+        &self,
+        unresisted_pos: &mut Option<Vec2>,
+        cam_pos: Vec2,
+        delta: Vec2,
+        pan_cam: &PanCam,
+        bounded_view_size: Vec2,
+        world_units_per_pixel: Vec2,
+    ) -> Vec2 {
+        let max_overscroll = self.max_overscroll * world_units_per_pixel;
+
+        let pos = unresisted_pos.unwrap_or_else(|| {
+            let safe_pos = clamp_to_safe_zone(cam_pos, pan_cam, bounded_view_size);
+            safe_pos + unresist_overscroll(cam_pos - safe_pos, max_overscroll)
+        }) - delta;
+        *unresisted_pos = Some(pos);
+
+        let safe_pos = clamp_to_safe_zone(pos, pan_cam, bounded_view_size);
+        safe_pos + resist_overscroll(pos - safe_pos, max_overscroll)
+    }
+
+    /// Springs a camera that isn't dragged back inside the bounds, and moves
+    /// it by `delta` as far as the bounds, or the overscroll that is left,
+    /// allow
+    ///
+    /// Returns the new camera position, and where `delta` would have moved it
+    /// without the bounds.
+    pub(crate) fn spring_back(
+        &self,
+        cam_pos: Vec2,
+        delta: Vec2,
+        pan_cam: &PanCam,
+        bounded_view_size: Vec2,
+        world_units_per_pixel: Vec2,
+        delta_secs: f32,
+    ) -> (Vec2, Vec2) {
+        let safe_pos = clamp_to_safe_zone(cam_pos, pan_cam, bounded_view_size);
+        let mut sprung_pos = cam_pos;
+        sprung_pos.smooth_nudge(&safe_pos, self.stiffness, delta_secs);
+
+        // Snap once we're within half a pixel
+        if ((sprung_pos - safe_pos) / world_units_per_pixel).length() < 0.5 {
+            sprung_pos = safe_pos;
+        }
+
+        // Only dragging stretches the bounds, other input stops at them or at
+        // the overscroll that is left
+        let proposed_pos = sprung_pos - delta;
+        let clamped_pos = clamp_to_safe_zone(proposed_pos, pan_cam, bounded_view_size);
+        let overscroll = proposed_pos - clamped_pos;
+        let left = sprung_pos - safe_pos;
+        let limit = |overscroll: f32, left: f32| {
+            if left >= 0. {
+                overscroll.clamp(0., left)
+            } else {
+                overscroll.clamp(left, 0.)
+            }
+        };
+        let new_pos = clamped_pos + vec2(limit(overscroll.x, left.x), limit(overscroll.y, left.y));
+        (new_pos, proposed_pos)
+    }
+}
+
+/// Applies rubber band resistance to a distance dragged past the bounds.
+///
+/// The result follows the drag one to one right at the edge, and
+/// asymptotically approaches `max_overscroll` the further the drag goes.
+fn resist_overscroll(overscroll: Vec2, max_overscroll: Vec2) -> Vec2 {
+    let resist = |overscroll: f32, max: f32| {
+        if max <= 0. {
+            return 0.;
+        }
+        overscroll.signum() * max * overscroll.abs() / (overscroll.abs() + max)
+    };
+    vec2(
+        resist(overscroll.x, max_overscroll.x),
+        resist(overscroll.y, max_overscroll.y),
+    )
+}
+
+/// The inverse of [`resist_overscroll`]: returns how far a drag must have gone
+/// past the bounds to end up at a given resisted overscroll.
+fn unresist_overscroll(resisted: Vec2, max_overscroll: Vec2) -> Vec2 {
+    let unresist = |resisted: f32, max: f32| {
+        if max <= 0. {
+            return 0.;
+        }
+        // Stay clear of the asymptote, e.g. when the bounds moved
+        let distance = resisted.abs().min(max * 0.99);
+        resisted.signum() * max * distance / (max - distance)
+    };
+    vec2(
+        unresist(resisted.x, max_overscroll.x),
+        unresist(resisted.y, max_overscroll.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        PanCamHitBounds, do_camera_movement,
+        test_utils::{input_app, mock_proj},
+    };
+
+    use super::*;
+
+    #[test]
+    fn overscroll_resistance_grows_with_distance() {
+        let max = vec2(100., 100.);
+        let small = resist_overscroll(vec2(1., -1.), max);
+        assert!(small.abs_diff_eq(vec2(1., -1.), 0.02));
+        let large = resist_overscroll(vec2(10000., 0.), max);
+        assert!(large.x < 100. && large.x > 99.);
+        assert_eq!(resist_overscroll(vec2(50., 50.), Vec2::ZERO), Vec2::ZERO);
+    }
+
+    #[test]
+    fn unresist_overscroll_inverts_resistance() {
+        let max = vec2(100., 40.);
+        let overscroll = vec2(-250., 30.);
+        let resisted = resist_overscroll(overscroll, max);
+        assert!(unresist_overscroll(resisted, max).abs_diff_eq(overscroll, 0.01));
+    }
+
+    #[test]
+    fn elastic_bounds_stop_keyboard_movement() {
+        #[derive(Resource, Default)]
+        struct Hits(Vec<PanCamHitBounds>);
+
+        let mut app = input_app();
+        app.init_resource::<Hits>()
+            .add_systems(Update, do_camera_movement)
+            .add_observer(|hit: On<PanCamHitBounds>, mut hits: ResMut<Hits>| {
+                hits.0.push(*hit);
+            });
+        let pan_cam = || PanCam {
+            min_x: -100.,
+            max_x: 100.,
+            min_y: -100.,
+            max_y: 100.,
+            elastic_bounds: Some(ElasticBounds::default()),
+            ..default()
+        };
+        let at_edge = app
+            .world_mut()
+            .spawn((
+                pan_cam(),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(50., 0., 0.),
+            ))
+            .id();
+        let overscrolled = app
+            .world_mut()
+            .spawn((
+                pan_cam(),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(80., 0., 0.),
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowRight);
+        app.update();
+        app.update();
+
+        // The keys don't stretch the bounds, but are reported to hit them
+        let transform = app.world().get::<Transform>(at_edge).unwrap();
+        assert_eq!(transform.translation, vec3(50., 0., 0.));
+        let hits = &app.world().resource::<Hits>().0;
+        assert!(hits.contains(&PanCamHitBounds {
+            entity: at_edge,
+            proposed_translation: vec2(70., 0.),
+            translation: vec2(50., 0.),
+        }));
+
+        // An overscrolled camera springs back rather than moving further out
+        let x = app
+            .world()
+            .get::<Transform>(overscrolled)
+            .unwrap()
+            .translation
+            .x;
+        assert!(x > 50. && x < 80., "{x}");
+    }
+}
@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+/// Triggered when a `PanCam` camera is moved by panning, zooming, following,
+/// flying or clamping to the bounds
+#[derive(EntityEvent, Debug, Clone, Copy, PartialEq)]
+pub struct PanCamMoved {
+    /// The camera that moved
+    pub entity: Entity,
+    /// The translation of the camera before it moved
+    pub previous_translation: Vec2,
+    /// The translation of the camera after it moved
+    pub translation: Vec2,
+}
+
+/// Triggered when the projection scale of a `PanCam` camera changes by
+/// zooming, flying or rotating
+#[derive(EntityEvent, Debug, Clone, Copy, PartialEq)]
+pub struct PanCamZoomed {
+    /// The camera that zoomed
+    pub entity: Entity,
+    /// The projection scale before zooming
+    pub old_scale: f32,
+    /// The projection scale after zooming
+    pub new_scale: f32,
+}
+
+/// Triggered when the user grabs a `PanCam` camera to drag it
+#[derive(EntityEvent, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanCamDragStarted {
+    /// The camera that was grabbed
+    pub entity: Entity,
+}
+
+/// Triggered when the user releases a `PanCam` camera they were dragging, or
+/// the drag is interrupted
+#[derive(EntityEvent, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanCamDragEnded {
+    /// The camera that was released
+    pub entity: Entity,
+}
+
+/// Triggered when the bounds of a `PanCam` camera stop it from moving where
+/// panning, zooming or clamping would have put it
+#[derive(EntityEvent, Debug, Clone, Copy, PartialEq)]
+pub struct PanCamHitBounds {
+    /// The camera that hit its bounds
+    pub entity: Entity,
+    /// Where the camera would have moved without bounds
+    pub proposed_translation: Vec2,
+    /// Where the bounds allowed the camera to move instead
+    pub translation: Vec2,
+}
+
+/// Triggered when a `PanCam` camera with [`PanCam::wrap_x`](crate::PanCam::wrap_x)
+/// or [`PanCam::wrap_y`](crate::PanCam::wrap_y) crosses its bounds and is
+/// moved to the other side of the world
+#[derive(EntityEvent, Debug, Clone, Copy, PartialEq)]
+pub struct PanCamWrapped {
+    /// The camera that wrapped around
+    pub entity: Entity,
+    /// How far the camera was moved, a multiple of the world size on each
+    /// axis
+    pub offset: Vec2,
+    /// The translation of the camera after it was moved
+    pub translation: Vec2,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::ButtonState;
+
+    use super::*;
+    use crate::{
+        PanCam, do_camera_movement, do_camera_zoom,
+        test_utils::{input_app, mock_proj, mouse_button, move_cursor, scale, scroll},
+    };
+
+    #[derive(Resource, Default)]
+    struct Triggered {
+        moved: Vec<PanCamMoved>,
+        zoomed: Vec<PanCamZoomed>,
+        drags_started: usize,
+        drags_ended: usize,
+    }
+
+    #[test]
+    fn dragging_and_zooming_trigger_events() {
+        let mut app = input_app();
+        app.init_resource::<Triggered>()
+            .add_systems(Update, (do_camera_movement, do_camera_zoom).chain())
+            .add_observer(|moved: On<PanCamMoved>, mut triggered: ResMut<Triggered>| {
+                triggered.moved.push(*moved);
+            })
+            .add_observer(
+                |zoomed: On<PanCamZoomed>, mut triggered: ResMut<Triggered>| {
+                    triggered.zoomed.push(*zoomed);
+                },
+            )
+            .add_observer(
+                |_: On<PanCamDragStarted>, mut triggered: ResMut<Triggered>| {
+                    triggered.drags_started += 1;
+                },
+            )
+            .add_observer(|_: On<PanCamDragEnded>, mut triggered: ResMut<Triggered>| {
+                triggered.drags_ended += 1;
+            });
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+        let triggered = |app: &App| {
+            let triggered = app.world().resource::<Triggered>();
+            (
+                triggered.moved.clone(),
+                triggered.zoomed.clone(),
+                triggered.drags_started,
+                triggered.drags_ended,
+            )
+        };
+
+        // Grabbing and holding still doesn't move the camera
+        move_cursor(&mut app, Some(vec2(50., 50.)));
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Pressed);
+        app.update();
+        app.update();
+        assert_eq!(triggered(&app), (vec![], vec![], 1, 0));
+
+        move_cursor(&mut app, Some(vec2(60., 50.)));
+        app.update();
+        app.update();
+        let moved = PanCamMoved {
+            entity: camera,
+            previous_translation: Vec2::ZERO,
+            translation: vec2(-10., 0.),
+        };
+        assert_eq!(triggered(&app), (vec![moved], vec![], 1, 0));
+
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Released);
+        app.update();
+        assert_eq!(triggered(&app), (vec![moved], vec![], 1, 1));
+
+        scroll(&mut app);
+        app.update();
+        app.update();
+        let (moved_events, zoomed, drags_started, drags_ended) = triggered(&app);
+        assert_eq!(
+            zoomed,
+            [PanCamZoomed {
+                entity: camera,
+                old_scale: 1.,
+                new_scale: scale(&app, camera),
+            }]
+        );
+        assert!(scale(&app, camera) < 1.);
+        // Zooming towards the cursor right of the middle moves the camera
+        assert_eq!(moved_events.len(), 2);
+        assert_eq!((drags_started, drags_ended), (1, 1));
+    }
+}
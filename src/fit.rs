@@ -0,0 +1,201 @@
+use bevy::{camera::primitives::Aabb, prelude::*};
+
+use crate::{PanCam, PanCamFlyTo, scale_to_fit, view_extent};
+
+/// Trigger this event to zoom and move a `PanCam` camera so that a world
+/// rectangle fits its viewport.
+///
+/// The resulting scale is kept within the camera's scale range and the
+/// position within its bounds, so the rectangle may end up only partially
+/// visible if it doesn't fit those.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct PanCamFitRect {
+    /// The camera to move
+    pub entity: Entity,
+    /// The world rectangle to fit
+    pub rect: Rect,
+    /// Free space to leave around the rectangle, in logical pixels
+    pub padding: f32,
+    /// How long the transition takes, in seconds. Zero jumps straight there.
+    pub duration: f32,
+}
+
+/// Trigger this event to zoom and move a `PanCam` camera so that a set of
+/// entities fits its viewport.
+///
+/// The fitted rectangle covers the [`Aabb`] of each entity, which Bevy
+/// computes for sprites and meshes, or just its [`GlobalTransform`]
+/// translation for entities without one. When that's a single point, e.g.
+/// for one entity without an [`Aabb`], the camera only moves there and keeps
+/// its zoom level.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct PanCamFitEntities {
+    /// The camera to move
+    pub entity: Entity,
+    /// The entities to fit
+    pub entities: Vec<Entity>,
+    /// Free space to leave around the entities, in logical pixels
+    pub padding: f32,
+    /// How long the transition takes, in seconds. Zero jumps straight there.
+    pub duration: f32,
+}
+
+/// What a [`PendingFit`] should fit
+enum FitTarget {
+    Rect(Rect),
+    Entities(Vec<Entity>),
+}
+
+/// A fit request waiting for the camera viewport and target transforms to be
+/// known
+#[derive(Component)]
+pub(crate) struct PendingFit {
+    target: FitTarget,
+    padding: f32,
+    duration: f32,
+}
+
+pub(crate) fn on_fit_rect(event: On<PanCamFitRect>, mut commands: Commands) {
+    commands.entity(event.entity).insert(PendingFit {
+        target: FitTarget::Rect(event.rect),
+        padding: event.padding,
+        duration: event.duration,
+    });
+}
+
+pub(crate) fn on_fit_entities(event: On<PanCamFitEntities>, mut commands: Commands) {
+    commands.entity(event.entity).insert(PendingFit {
+        target: FitTarget::Entities(event.entities.clone()),
+        padding: event.padding,
+        duration: event.duration,
+    });
+}
+
+pub(crate) fn do_camera_fit(
+    mut commands: Commands,
+    cameras: Query<(Entity, &PendingFit, &Camera, &Projection, &Transform), With<PanCam>>,
+    targets: Query<(&GlobalTransform, Option<&Aabb>)>,
+) {
+    for (entity, pending_fit, camera, projection, transform) in &cameras {
+        let Projection::Orthographic(proj) = projection else {
+            warn_once!("Tried to fit a camera without an orthographic projection.");
+            commands.entity(entity).remove::<PendingFit>();
+            continue;
+        };
+
+        let Some(view_size) = camera.logical_viewport_size() else {
+            continue;
+        };
+
+        commands.entity(entity).remove::<PendingFit>();
+
+        let rect = match &pending_fit.target {
+            FitTarget::Rect(rect) => *rect,
+            FitTarget::Entities(entities) => {
+                let Some(rect) = targets
+                    .iter_many(entities)
+                    .map(|(transform, aabb)| entity_rect(transform, aabb))
+                    .reduce(|a, b| a.union(b))
+                else {
+                    continue;
+                };
+                rect
+            }
+        };
+
+        commands.trigger(PanCamFlyTo {
+            entity,
+            translation: rect.center(),
+            // A rotated view has to fit the box around the rotated rectangle.
+            // A single point has no size to fit, so it keeps the zoom level.
+            scale: (rect.size() != Vec2::ZERO).then(|| {
+                scale_to_fit(
+                    view_extent(rect.size(), transform.rotation),
+                    proj,
+                    view_size,
+                    pending_fit.padding,
+                )
+            }),
+            duration: pending_fit.duration,
+            easing: EaseFunction::CubicInOut,
+        });
+    }
+}
+
+/// The world rectangle covered by an entity in the xy plane
+fn entity_rect(transform: &GlobalTransform, aabb: Option<&Aabb>) -> Rect {
+    let Some(aabb) = aabb else {
+        return Rect::from_center_size(transform.translation().truncate(), Vec2::ZERO);
+    };
+
+    let (center, half_extents) = (Vec3::from(aabb.center), Vec3::from(aabb.half_extents));
+    [
+        vec3(-1., -1., 0.),
+        vec3(-1., 1., 0.),
+        vec3(1., -1., 0.),
+        vec3(1., 1., 0.),
+    ]
+    .into_iter()
+    .map(|corner| {
+        transform
+            .transform_point(center + corner * half_extents)
+            .truncate()
+    })
+    .fold(
+        Rect::from_center_size(transform.transform_point(center).truncate(), Vec2::ZERO),
+        |rect, point| rect.union_point(point),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{mock_proj, viewport_camera};
+
+    #[test]
+    fn fitting_a_single_point_keeps_the_zoom_level() {
+        #[derive(Resource, Default)]
+        struct Flights(Vec<PanCamFlyTo>);
+
+        let mut app = App::new();
+        app.init_resource::<Flights>()
+            .add_systems(Update, do_camera_fit)
+            .add_observer(on_fit_entities)
+            .add_observer(|fly_to: On<PanCamFlyTo>, mut flights: ResMut<Flights>| {
+                flights.0.push(fly_to.clone());
+            });
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                viewport_camera(0, 100, 0),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+        let targets = [
+            app.world_mut()
+                .spawn(GlobalTransform::from_xyz(30., 40., 0.))
+                .id(),
+            app.world_mut()
+                .spawn(GlobalTransform::from_xyz(30., 40., 0.))
+                .id(),
+        ];
+
+        for entities in [vec![targets[0]], targets.to_vec()] {
+            app.world_mut().trigger(PanCamFitEntities {
+                entity: camera,
+                entities,
+                padding: 10.,
+                duration: 0.,
+            });
+            app.update();
+        }
+
+        let flights = &app.world().resource::<Flights>().0;
+        assert_eq!(flights.len(), 2);
+        for flight in flights {
+            assert_eq!(flight.translation, vec2(30., 40.));
+            assert_eq!(flight.scale, None);
+        }
+    }
+}
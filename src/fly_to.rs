@@ -0,0 +1,272 @@
+use bevy::{camera::RenderTarget, prelude::*};
+
+use crate::{
+    DragState, PanCam, PanCamWindows, PanCamZoomed, SnapState, ZoomState, clamp_to_safe_zone,
+    constrain_proj_scale, trigger_moved, view_extent, wrap::shortest_offset,
+};
+
+/// Trigger this event to smoothly move a `PanCam` camera to a position and
+/// zoom level.
+///
+/// The target is kept within the camera's bounds and scale range. The
+/// transition is cancelled when the user grabs the camera, or replaced when
+/// another `PanCamFlyTo` is triggered for the same camera.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct PanCamFlyTo {
+    /// The camera to move
+    pub entity: Entity,
+    /// The world position the camera should be centered on
+    pub translation: Vec2,
+    /// The orthographic projection scale to end up at, or `None` to keep the
+    /// current scale
+    pub scale: Option<f32>,
+    /// How long the transition takes, in seconds
+    pub duration: f32,
+    /// The easing curve applied over the duration of the transition
+    pub easing: EaseFunction,
+}
+
+/// An ongoing [`PanCamFlyTo`] transition
+#[derive(Component)]
+pub(crate) struct Flight {
+    start_translation: Vec2,
+    start_scale: f32,
+    target_translation: Vec2,
+    target_scale: f32,
+    duration: f32,
+    elapsed: f32,
+    easing: EaseFunction,
+}
+
+impl Flight {
+    /// Moves the transition along with a camera that wrapped around the world
+    pub(crate) fn shift(&mut self, offset: Vec2) {
+        self.start_translation += offset;
+        self.target_translation += offset;
+    }
+}
+
+pub(crate) fn on_fly_to(
+    event: On<PanCamFlyTo>,
+    mut commands: Commands,
+    mut query: Query<(
+        &PanCam,
+        &Transform,
+        &Projection,
+        &mut DragState,
+        &mut ZoomState,
+        &SnapState,
+    )>,
+) {
+    let Ok((
+        pan_cam,
+        transform,
+        Projection::Orthographic(proj),
+        mut drag_state,
+        mut zoom_state,
+        snap_state,
+    )) = query.get_mut(event.entity)
+    else {
+        warn_once!("Tried to fly an unsupported entity.");
+        return;
+    };
+
+    // Don't let an ongoing glide or smooth zoom fight the transition
+    drag_state.velocity = Vec2::ZERO;
+    zoom_state.target_scale = None;
+
+    // Fly the shorter way around a wrapping world
+    let start_translation = snap_state.position(transform.translation.truncate());
+    let target_translation =
+        start_translation + shortest_offset(event.translation - start_translation, pan_cam);
+
+    commands.entity(event.entity).insert(Flight {
+        start_translation,
+        start_scale: proj.scale,
+        target_translation,
+        target_scale: event.scale.unwrap_or(proj.scale),
+        duration: event.duration,
+        elapsed: 0.,
+        easing: event.easing,
+    });
+}
+
+pub(crate) fn do_camera_fly_to(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &PanCam,
+        &mut Flight,
+        &Camera,
+        &RenderTarget,
+        &mut Projection,
+        &mut Transform,
+    )>,
+    mut states: Query<(&DragState, &mut SnapState)>,
+    windows: PanCamWindows,
+    time: Res<Time<Real>>,
+) {
+    for (entity, pan_cam, mut flight, camera, render_target, mut proj, mut transform) in &mut query
+    {
+        let Projection::Orthographic(proj) = &mut *proj else {
+            commands.entity(entity).remove::<Flight>();
+            continue;
+        };
+
+        let Ok((drag_state, mut snap_state)) = states.get_mut(entity) else {
+            continue;
+        };
+        if !pan_cam.enabled || drag_state.grabbed {
+            commands.entity(entity).remove::<Flight>();
+            continue;
+        }
+
+        let Some((_, window)) = windows.camera_window(render_target) else {
+            continue;
+        };
+        let view_size = camera.logical_viewport_size().unwrap_or(window.size());
+
+        flight.elapsed += time.delta_secs();
+        let t = if flight.duration > 0. {
+            (flight.elapsed / flight.duration).min(1.)
+        } else {
+            1.
+        };
+        let progress = flight.easing.sample_clamped(t);
+
+        // Interpolate the scale geometrically, so zooming feels equally fast
+        // at all zoom levels
+        let prev_scale = proj.scale;
+        let mut target_proj = proj.clone();
+        target_proj.scale = flight.target_scale;
+        constrain_proj_scale(&mut target_proj, pan_cam, view_size, transform.rotation);
+        proj.scale = flight.start_scale * (target_proj.scale / flight.start_scale).powf(progress);
+        if proj.scale != prev_scale {
+            commands.trigger(PanCamZoomed {
+                entity,
+                old_scale: prev_scale,
+                new_scale: proj.scale,
+            });
+        }
+
+        let area_size = proj.area.size() / prev_scale * proj.scale;
+        let target_area_size = proj.area.size() / prev_scale * target_proj.scale;
+        let target_translation = clamp_to_safe_zone(
+            flight.target_translation,
+            pan_cam,
+            view_extent(target_area_size, transform.rotation),
+        );
+        let translation = flight.start_translation.lerp(target_translation, progress);
+
+        let new_translation = clamp_to_safe_zone(
+            translation,
+            pan_cam,
+            view_extent(area_size, transform.rotation),
+        );
+        let pixel_size = area_size / view_size / window.scale_factor();
+        let previous_translation = transform.translation.truncate();
+        transform.translation = snap_state
+            .apply(new_translation, pan_cam, pixel_size, transform.rotation)
+            .extend(transform.translation.z);
+        trigger_moved(&mut commands, entity, previous_translation, &transform);
+
+        if t >= 1. {
+            commands.entity(entity).remove::<Flight>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{input_app, mock_proj, scale},
+        wrap,
+    };
+
+    use super::*;
+
+    #[test]
+    fn flying_eases_within_bounds_until_grabbed() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_fly_to)
+            .add_observer(on_fly_to);
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    min_x: -100.,
+                    max_x: 100.,
+                    min_y: -100.,
+                    max_y: 100.,
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+
+        app.update();
+        app.world_mut().trigger(PanCamFlyTo {
+            entity: camera,
+            translation: vec2(500., 0.),
+            scale: Some(0.5),
+            duration: 1.,
+            easing: EaseFunction::Linear,
+        });
+        app.update();
+
+        // The target is clamped for the view at the target scale, and the
+        // scale changes geometrically
+        let x = app.world().get::<Transform>(camera).unwrap().translation.x;
+        assert!((x - 7.5).abs() < 1e-3, "{x}");
+        assert!((scale(&app, camera) - 0.5f32.powf(0.1)).abs() < 1e-5);
+
+        app.world_mut()
+            .get_mut::<DragState>(camera)
+            .unwrap()
+            .grabbed = true;
+        app.update();
+        app.update();
+        assert!(app.world().get::<Flight>(camera).is_none());
+        let x = app.world().get::<Transform>(camera).unwrap().translation.x;
+        assert!((x - 7.5).abs() < 1e-3, "{x}");
+    }
+
+    #[test]
+    fn flying_takes_the_short_way_around_a_wrapping_world() {
+        let mut app = input_app();
+        app.add_systems(Update, (do_camera_fly_to, wrap::wrap_cameras).chain())
+            .add_observer(on_fly_to);
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    min_x: -100.,
+                    max_x: 100.,
+                    wrap_x: true,
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(90., 0., 0.),
+            ))
+            .id();
+
+        app.update();
+        app.world_mut().trigger(PanCamFlyTo {
+            entity: camera,
+            translation: vec2(-90., 0.),
+            scale: None,
+            duration: 1.,
+            easing: EaseFunction::Linear,
+        });
+        app.update();
+        let x = app.world().get::<Transform>(camera).unwrap().translation.x;
+        assert!((x - 92.).abs() < 1e-3, "{x}");
+
+        // Flying on past the seam wraps the camera and its flight
+        for _ in 0..9 {
+            app.update();
+        }
+        let x = app.world().get::<Transform>(camera).unwrap().translation.x;
+        assert!((x + 90.).abs() < 1e-3, "{x}");
+    }
+}
@@ -0,0 +1,346 @@
+use bevy::{camera::RenderTarget, prelude::*};
+
+use crate::{
+    DragState, PanCam, PanCamWindows, SnapState, clamp_to_safe_zone, fly_to::Flight, trigger_moved,
+    view_extent, wrap::shortest_offset,
+};
+
+/// A component that makes a `PanCam` camera track a target entity
+///
+/// Panning the camera manually pauses following, see
+/// [`PanCamFollow::paused`].
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+#[require(FollowState)]
+pub struct PanCamFollow {
+    /// The entity whose [`GlobalTransform`] the camera follows
+    pub target: Entity,
+    /// The size of the rectangle around the center of the view, in logical
+    /// pixels, within which the target can move without the camera following
+    pub dead_zone: Vec2,
+    /// When set, the camera eases towards the followed position instead of
+    /// snapping to it
+    ///
+    /// The value is the exponential decay rate per second of the remaining
+    /// distance; higher values follow more tightly.
+    pub smoothing: Option<f32>,
+    /// How many seconds ahead of the target to aim the camera, based on the
+    /// velocity of the target
+    pub lookahead: f32,
+    /// How many seconds after the user last panned the camera following
+    /// resumes automatically
+    ///
+    /// When `None`, following stays paused until [`PanCamFollow::paused`] is
+    /// reset.
+    pub resume_after: Option<f32>,
+    /// Whether following is paused because the user panned the camera
+    ///
+    /// Set this back to `false` to resume following.
+    pub paused: bool,
+}
+
+impl PanCamFollow {
+    /// Follows `target` tightly, without dead zone or lookahead, and resumes
+    /// two seconds after the user last panned the camera
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            dead_zone: Vec2::ZERO,
+            smoothing: Some(10.),
+            lookahead: 0.,
+            resume_after: Some(2.),
+            paused: false,
+        }
+    }
+}
+
+/// Tracks the motion of the followed target
+#[derive(Component, Default)]
+pub(crate) struct FollowState {
+    last_target_pos: Option<Vec2>,
+    target_velocity: Vec2,
+}
+
+impl FollowState {
+    /// Keeps the last target position in the same copy of a wrapping world as
+    /// a camera that wrapped around it
+    pub(crate) fn shift(&mut self, offset: Vec2) {
+        if let Some(last_target_pos) = &mut self.last_target_pos {
+            *last_target_pos += offset;
+        }
+    }
+}
+
+pub(crate) fn do_camera_follow(
+    mut query: Query<(
+        Entity,
+        &PanCam,
+        &mut PanCamFollow,
+        &mut FollowState,
+        &Camera,
+        &Projection,
+        &mut Transform,
+    )>,
+    mut pan_cams: Query<(&DragState, &RenderTarget, &mut SnapState, Has<Flight>)>,
+    targets: Query<&GlobalTransform>,
+    windows: PanCamWindows,
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+) {
+    for (entity, pan_cam, mut follow, mut follow_state, camera, projection, mut transform) in
+        &mut query
+    {
+        let Ok(target_transform) = targets.get(follow.target) else {
+            continue;
+        };
+        let target_pos = target_transform.translation().truncate();
+
+        // Keep track of the target velocity even while paused, so lookahead
+        // is right as soon as following resumes. Targets that wrap around the
+        // world move by the shortest way.
+        if let Some(last_target_pos) = follow_state.last_target_pos
+            && time.delta_secs() > 0.
+        {
+            let velocity =
+                shortest_offset(target_pos - last_target_pos, pan_cam) / time.delta_secs();
+            follow_state
+                .target_velocity
+                .smooth_nudge(&velocity, 10., time.delta_secs());
+        }
+        follow_state.last_target_pos = Some(target_pos);
+
+        let Ok((drag_state, render_target, mut snap_state, flying)) = pan_cams.get_mut(entity)
+        else {
+            continue;
+        };
+
+        if let Some(last_user_pan) = drag_state.last_user_pan {
+            if last_user_pan == time.elapsed_secs() {
+                follow.paused = true;
+            } else if follow.paused
+                && follow
+                    .resume_after
+                    .is_some_and(|resume_after| time.elapsed_secs() - last_user_pan >= resume_after)
+            {
+                follow.paused = false;
+            }
+        }
+
+        // Let programmatic transitions finish before following again
+        if follow.paused || flying || !pan_cam.enabled {
+            continue;
+        }
+
+        let Projection::Orthographic(proj) = projection else {
+            continue;
+        };
+        let Some((_, window)) = windows.camera_window(render_target) else {
+            continue;
+        };
+        let viewport_size = camera.logical_viewport_size().unwrap_or(window.size());
+        let world_units_per_pixel = proj.area.size() / viewport_size;
+
+        let cam_pos = snap_state.position(transform.translation.truncate());
+        // Go the shorter way around a wrapping world
+        let focus = cam_pos
+            + shortest_offset(
+                target_pos + follow_state.target_velocity * follow.lookahead - cam_pos,
+                pan_cam,
+            );
+
+        // Only move as far as needed to bring the focus back into the dead
+        // zone, which is aligned with the possibly rotated view
+        let half_dead_zone = follow.dead_zone / 2. * world_units_per_pixel;
+        let offset = (transform.rotation.inverse() * (focus - cam_pos).extend(0.)).truncate();
+        let correction = offset - offset.clamp(-half_dead_zone, half_dead_zone);
+        let desired_pos = cam_pos + (transform.rotation * correction.extend(0.)).truncate();
+
+        let mut new_pos = desired_pos;
+        if let Some(smoothing) = follow.smoothing {
+            new_pos = cam_pos;
+            new_pos.smooth_nudge(&desired_pos, smoothing, time.delta_secs());
+        }
+
+        let new_pos = clamp_to_safe_zone(
+            new_pos,
+            pan_cam,
+            view_extent(proj.area.size(), transform.rotation),
+        );
+        if new_pos != cam_pos {
+            let pixel_size = world_units_per_pixel / window.scale_factor();
+            let previous_translation = transform.translation.truncate();
+            transform.translation = snap_state
+                .apply(new_pos, pan_cam, pixel_size, transform.rotation)
+                .extend(transform.translation.z);
+            trigger_moved(&mut commands, entity, previous_translation, &transform);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::ButtonState;
+
+    use crate::{
+        do_camera_movement,
+        test_utils::{input_app, mock_proj, mouse_button, move_cursor},
+        wrap,
+    };
+
+    use super::*;
+
+    #[test]
+    fn following_ignores_the_target_within_the_dead_zone() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_follow);
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(15., 0., 0.))
+            .id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                PanCamFollow {
+                    dead_zone: vec2(40., 40.),
+                    smoothing: None,
+                    ..PanCamFollow::new(target)
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, Vec3::ZERO);
+
+        // Leaving the dead zone only moves the camera far enough to bring the
+        // target back to its edge
+        *app.world_mut().get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(30., -25., 0.);
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(10., -5., 0.));
+    }
+
+    #[test]
+    fn following_snaps_to_pixels() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_follow);
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(10.3, 0., 0.))
+            .id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    pixel_snap: true,
+                    ..default()
+                },
+                PanCamFollow {
+                    smoothing: None,
+                    ..PanCamFollow::new(target)
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(10., 0., 0.));
+
+        *app.world_mut().get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(10.6, 0., 0.);
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(11., 0., 0.));
+    }
+
+    #[test]
+    fn panning_pauses_following_until_it_resumes() {
+        let mut app = input_app();
+        app.add_systems(Update, (do_camera_movement, do_camera_follow).chain());
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(100., 0., 0.))
+            .id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                PanCamFollow {
+                    smoothing: None,
+                    resume_after: Some(0.25),
+                    ..PanCamFollow::new(target)
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+        let paused = |app: &App| app.world().get::<PanCamFollow>(camera).unwrap().paused;
+        let x = |app: &App| app.world().get::<Transform>(camera).unwrap().translation.x;
+
+        app.update();
+        assert_eq!(x(&app), 100.);
+
+        // Clicking without dragging doesn't pause following
+        move_cursor(&mut app, Some(vec2(50., 50.)));
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Pressed);
+        app.update();
+        app.update();
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Released);
+        app.update();
+        assert!(!paused(&app));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowLeft);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::ArrowLeft);
+        assert!(paused(&app));
+        assert_eq!(x(&app), 80.);
+
+        app.update();
+        app.update();
+        assert!(paused(&app));
+        assert_eq!(x(&app), 80.);
+
+        // Following resumes once the user hasn't panned for a while
+        app.update();
+        assert!(!paused(&app));
+        assert_eq!(x(&app), 100.);
+    }
+
+    #[test]
+    fn following_takes_the_short_way_around_a_wrapping_world() {
+        let mut app = input_app();
+        app.add_systems(Update, (do_camera_follow, wrap::wrap_cameras).chain());
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(-90., 0., 0.))
+            .id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    min_x: -100.,
+                    max_x: 100.,
+                    wrap_x: true,
+                    ..default()
+                },
+                PanCamFollow::new(target),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(90., 0., 0.),
+            ))
+            .id();
+
+        app.update();
+        app.update();
+
+        // Easing towards the target crossed the seam instead of the world
+        let x = app.world().get::<Transform>(camera).unwrap().translation.x;
+        assert!(x > -100. && x < -90., "{x}");
+    }
+}
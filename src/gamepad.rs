@@ -0,0 +1,161 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// An analog stick on a gamepad
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum GamepadStick {
+    /// The left analog stick
+    Left,
+    /// The right analog stick
+    Right,
+}
+
+impl GamepadStick {
+    fn position(self, gamepad: &Gamepad) -> Vec2 {
+        match self {
+            GamepadStick::Left => gamepad.left_stick(),
+            GamepadStick::Right => gamepad.right_stick(),
+        }
+    }
+}
+
+/// Which gamepad input zooms the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum GamepadZoom {
+    /// The right trigger zooms in and the left trigger zooms out
+    Triggers,
+    /// Pushing the stick up zooms in and pulling it down zooms out
+    Stick(GamepadStick),
+}
+
+/// Gamepad bindings for panning and zooming the camera
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct GamepadControls {
+    /// The gamepad that controls the camera, or `None` to respond to all
+    /// connected gamepads
+    pub gamepad: Option<Entity>,
+    /// The stick that pans the camera
+    ///
+    /// At full deflection the camera moves at [`PanCam::speed`](crate::PanCam::speed).
+    pub pan_stick: Option<GamepadStick>,
+    /// The input that zooms the camera
+    pub zoom: Option<GamepadZoom>,
+    /// How much the scale changes per second at full deflection, as a factor
+    ///
+    /// `2.` halves or doubles the scale every second.
+    pub zoom_speed: f32,
+    /// How far a stick or trigger has to be moved before it has any effect,
+    /// as a fraction of its full range
+    pub deadzone: f32,
+    /// Shapes how the response ramps up between the deadzone and full
+    /// deflection
+    ///
+    /// `1.` responds linearly, higher values give finer control close to the
+    /// deadzone.
+    pub response_curve: f32,
+}
+
+impl Default for GamepadControls {
+    fn default() -> Self {
+        Self {
+            gamepad: None,
+            pan_stick: Some(GamepadStick::Left),
+            zoom: Some(GamepadZoom::Triggers),
+            zoom_speed: 2.,
+            deadzone: 0.15,
+            response_curve: 2.,
+        }
+    }
+}
+
+impl GamepadControls {
+    /// Applies the deadzone and response curve to an analog value in `-1..=1`
+    pub(crate) fn response(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        if magnitude <= self.deadzone {
+            return 0.;
+        }
+
+        let remapped = ((magnitude - self.deadzone) / (1. - self.deadzone)).min(1.);
+        value.signum() * remapped.powf(self.response_curve)
+    }
+
+    /// Returns the pan direction for a gamepad, with a length of at most one
+    fn direction(&self, gamepad: &Gamepad) -> Vec2 {
+        let Some(stick) = self.pan_stick else {
+            return Vec2::ZERO;
+        };
+
+        // Apply the deadzone to the length of the stick vector, so diagonal
+        // movement isn't snapped towards the axes
+        let position = stick.position(gamepad);
+        position.normalize_or_zero() * self.response(position.length())
+    }
+
+    /// Returns how much a gamepad zooms in, in `-1..=1`
+    fn zoom(&self, gamepad: &Gamepad) -> f32 {
+        let value = match self.zoom {
+            Some(GamepadZoom::Triggers) => {
+                let trigger = |button| gamepad.get(button).unwrap_or(0.);
+                trigger(GamepadButton::RightTrigger2) - trigger(GamepadButton::LeftTrigger2)
+            }
+            Some(GamepadZoom::Stick(stick)) => stick.position(gamepad).y,
+            None => 0.,
+        };
+        self.response(value)
+    }
+}
+
+/// The connected gamepads
+#[derive(SystemParam)]
+pub(crate) struct Gamepads<'w, 's> {
+    gamepads: Query<'w, 's, (Entity, &'static Gamepad)>,
+}
+
+impl Gamepads<'_, '_> {
+    fn controlling<'a>(&'a self, controls: &GamepadControls) -> impl Iterator<Item = &'a Gamepad> {
+        let gamepad = controls.gamepad;
+        self.gamepads
+            .iter()
+            .filter(move |(entity, _)| gamepad.is_none_or(|gamepad| gamepad == *entity))
+            .map(|(_, gamepad)| gamepad)
+    }
+
+    /// Returns the combined pan direction of the gamepads controlling a
+    /// camera, with a length of at most one
+    pub(crate) fn direction(&self, controls: &GamepadControls) -> Vec2 {
+        self.controlling(controls)
+            .map(|gamepad| controls.direction(gamepad))
+            .sum::<Vec2>()
+            .clamp_length_max(1.)
+    }
+
+    /// Returns the factor the gamepads controlling a camera change its scale
+    /// by over `delta_secs`
+    pub(crate) fn scale_factor(&self, controls: &GamepadControls, delta_secs: f32) -> f32 {
+        let zoom = self
+            .controlling(controls)
+            .map(|gamepad| controls.zoom(gamepad))
+            .sum::<f32>()
+            .clamp(-1., 1.);
+        controls.zoom_speed.powf(-zoom * delta_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamepad_response_ignores_deadzone_and_follows_curve() {
+        let controls = GamepadControls {
+            deadzone: 0.2,
+            response_curve: 2.,
+            ..default()
+        };
+        assert_eq!(controls.response(0.1), 0.);
+        assert_eq!(controls.response(-0.2), 0.);
+        assert!((controls.response(0.6) - 0.25).abs() < 0.001);
+        assert!((controls.response(-0.6) + 0.25).abs() < 0.001);
+        assert_eq!(controls.response(1.), 1.);
+    }
+}
@@ -1,22 +1,57 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+mod edge_scroll;
 #[cfg(feature = "internal_bevy_egui")]
 mod egui_support;
+mod elastic;
+mod events;
+mod fit;
+mod fly_to;
+mod follow;
+mod gamepad;
+mod momentum;
 mod normalized_zoom_inputs;
+mod rotation;
+mod shape_bounds;
+mod snap;
+mod state;
+#[cfg(test)]
+mod test_utils;
+mod touch;
+mod wrap;
+mod zoom_steps;
 use bevy::{
-    camera::CameraProjection,
-    input::{gestures::PinchGesture, mouse::MouseWheel},
-    math::{
-        Rect,
-        bounding::{Aabb2d, BoundingVolume},
-        vec2,
+    camera::{CameraProjection, RenderTarget},
+    ecs::{entity::ContainsEntity, system::SystemParam},
+    input::{
+        gestures::{PinchGesture, RotationGesture},
+        mouse::MouseWheel,
     },
+    math::{Rect, bounding::Aabb2d, vec2},
+    platform::collections::HashMap,
     prelude::*,
     window::PrimaryWindow,
 };
-use normalized_zoom_inputs::NormalizedZoomInputs;
-use std::ops::RangeInclusive;
+pub use edge_scroll::EdgeScroll;
+pub use elastic::ElasticBounds;
+pub use events::{
+    PanCamDragEnded, PanCamDragStarted, PanCamHitBounds, PanCamMoved, PanCamWrapped, PanCamZoomed,
+};
+pub use fit::{PanCamFitEntities, PanCamFitRect};
+pub use fly_to::PanCamFlyTo;
+pub use follow::PanCamFollow;
+use gamepad::Gamepads;
+pub use gamepad::{GamepadControls, GamepadStick, GamepadZoom};
+pub use momentum::Momentum;
+use normalized_zoom_inputs::{NormalizedZoomInputs, pixel_scroll, pixel_scroll_pans};
+pub use rotation::RotationControls;
+pub use shape_bounds::{BoundsShape, CircleBounds, ConvexPolygonBounds, KeepInside, ShapeBounds};
+use snap::SnapState;
+pub use state::{BoundsContact, PanCamState};
+use std::{collections::VecDeque, sync::Arc};
+use touch::{TouchInputs, TouchWindows};
+use zoom_steps::{ContinuousZoom, step_zoom};
 
 /// Plugin that adds the necessary systems for `PanCam` components to work
 #[derive(Default)]
@@ -26,7 +61,19 @@ pub struct PanCamPlugin;
 #[derive(Debug, Clone, Copy, SystemSet, PartialEq, Eq, Hash)]
 pub struct PanCamSystems;
 
+/// The part of [`PanCamSystems`] that reacts to user input
+///
+/// With one of the `bevy_egui` features, only these systems pause while egui
+/// has the focus. Fly-to, fit, follow, wrapping and automatic clamping keep
+/// running.
+#[derive(Debug, Clone, Copy, SystemSet, PartialEq, Eq, Hash)]
+pub struct PanCamInputSystems;
+
 /// Trigger this event after changing the camera bounds potentially outside the safe zone.
+///
+/// The plugin triggers it by itself when the bounds, scale range, viewport,
+/// projection or window size of a camera change, so this is only needed to
+/// clamp right away, before `PanCamSystems` runs.
 #[derive(EntityEvent)]
 pub struct PanCamClampBounds {
     /// The entity that triggered the event
@@ -108,15 +155,269 @@ impl DirectionKeys {
     }
 }
 
+/// Which keys zoom the camera in and out for keyboard zooming
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub struct ZoomKeys {
+    /// The keys that zoom the camera in
+    pub zoom_in: Vec<KeyCode>,
+    /// The keys that zoom the camera out
+    pub zoom_out: Vec<KeyCode>,
+}
+
+impl ZoomKeys {
+    /// No keys zoom the camera
+    pub const NONE: Self = Self {
+        zoom_in: vec![],
+        zoom_out: vec![],
+    };
+
+    /// The camera is zoomed by the plus and minus keys, on the main keyboard
+    /// and the numpad
+    pub fn plus_minus() -> Self {
+        Self {
+            zoom_in: vec![KeyCode::Equal, KeyCode::NumpadAdd],
+            zoom_out: vec![KeyCode::Minus, KeyCode::NumpadSubtract],
+        }
+    }
+
+    /// The camera is zoomed by the page up and page down keys
+    pub fn page_up_down() -> Self {
+        Self {
+            zoom_in: vec![KeyCode::PageUp],
+            zoom_out: vec![KeyCode::PageDown],
+        }
+    }
+
+    /// The camera is zoomed by the plus, minus, page up and page down keys
+    pub fn plus_minus_and_page_up_down() -> Self {
+        Self {
+            zoom_in: vec![KeyCode::Equal, KeyCode::NumpadAdd, KeyCode::PageUp],
+            zoom_out: vec![KeyCode::Minus, KeyCode::NumpadSubtract, KeyCode::PageDown],
+        }
+    }
+
+    /// Returns `1.` when zooming in, `-1.` when zooming out and `0.` otherwise
+    fn direction(&self, keyboard_buttons: &Res<ButtonInput<KeyCode>>) -> f32 {
+        let mut direction = 0.;
+
+        if self
+            .zoom_in
+            .iter()
+            .any(|key| keyboard_buttons.pressed(*key))
+        {
+            direction += 1.;
+        }
+
+        if self
+            .zoom_out
+            .iter()
+            .any(|key| keyboard_buttons.pressed(*key))
+        {
+            direction -= 1.;
+        }
+
+        direction
+    }
+
+    /// Returns `1` when a zoom in key was just pressed, `-1` for zoom out and
+    /// `0` otherwise
+    fn just_pressed_direction(&self, keyboard_buttons: &Res<ButtonInput<KeyCode>>) -> i32 {
+        let mut direction = 0;
+
+        if self
+            .zoom_in
+            .iter()
+            .any(|key| keyboard_buttons.just_pressed(*key))
+        {
+            direction += 1;
+        }
+
+        if self
+            .zoom_out
+            .iter()
+            .any(|key| keyboard_buttons.just_pressed(*key))
+        {
+            direction -= 1;
+        }
+
+        direction
+    }
+}
+
+/// What scrolling does to the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum ScrollBehavior {
+    /// All scrolling zooms the camera
+    #[default]
+    Zoom,
+    /// Pixel based scrolling, which trackpads send for two finger swipes, pans
+    /// the camera on both axes, and only zooms while Ctrl or Cmd is held
+    ///
+    /// Line based scrolling from mouse wheels and pinch gestures keep zooming.
+    PixelScrollPans,
+}
+
+/// Settings for how scrolling is turned into zooming
+///
+/// Mouse wheels scroll either by lines or, for high resolution wheels and
+/// trackpads, by pixels. Both are converted to pixels before zooming, and can
+/// be tuned separately when devices zoom too fast or too slow.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct WheelZoom {
+    /// How much zoom one pixel of scrolling gives, relative to a
+    /// [`PinchGesture`] of the same size
+    ///
+    /// Wheel deltas are roughly 1000 times larger than equivalent pinches,
+    /// hence the default of `0.001`.
+    pub normalize_factor: f32,
+    /// How many pixels one line of scrolling counts as
+    pub pixels_per_line: f32,
+    /// Adjust the zoom sensitivity of line based scrolling
+    pub line_sensitivity: f32,
+    /// Adjust the zoom sensitivity of pixel based scrolling
+    pub pixel_sensitivity: f32,
+    /// Zoom in when scrolling down instead of up
+    pub invert: bool,
+}
+
+impl Default for WheelZoom {
+    fn default() -> Self {
+        Self {
+            normalize_factor: 0.001,
+            pixels_per_line: 100.,
+            line_sensitivity: 1.,
+            pixel_sensitivity: 1.,
+            invert: false,
+        }
+    }
+}
+
+/// What a camera does on an axis where its bounds are smaller than the view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum SmallBounds {
+    /// Zooming out is limited so the view never gets bigger than the bounds
+    ///
+    /// Should the view be bigger anyway, the bounds are centered in it.
+    #[default]
+    ZoomIn,
+    /// The view may get bigger than the bounds, which are then centered in it
+    Center,
+    /// The view may get bigger than the bounds, which are then aligned to its
+    /// left or bottom edge
+    AlignMin,
+    /// The view may get bigger than the bounds, which are then aligned to its
+    /// right or top edge
+    AlignMax,
+    /// The view may get bigger than the bounds, and moves freely as long as
+    /// the bounds stay inside it
+    Free,
+}
+
+/// Extra room around the bounds of a camera that the view may scroll into,
+/// e.g. to show some context around the edges of a level
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum BoundsPadding {
+    /// Padding in world units
+    World(f32),
+    /// Padding as a fraction of the view size, e.g. `0.2` for 20% of the
+    /// viewport
+    ViewportFraction(f32),
+}
+
+impl BoundsPadding {
+    /// No padding
+    pub const NONE: Self = Self::World(0.);
+
+    /// Returns the part of a view of the given world size that has to stay
+    /// within the bounds, which is the view without the padding on each side
+    fn inner_view_size(&self, view_size: Vec2) -> Vec2 {
+        let padding = match *self {
+            BoundsPadding::World(padding) => Vec2::splat(padding),
+            BoundsPadding::ViewportFraction(fraction) => view_size * fraction,
+        };
+        (view_size - 2. * padding).max(Vec2::ZERO)
+    }
+
+    /// Returns the largest view whose [inner size](Self::inner_view_size)
+    /// fits bounds of the given size
+    fn max_view_size(&self, bounded_area_size: Vec2) -> Vec2 {
+        match *self {
+            BoundsPadding::World(padding) => bounded_area_size + 2. * padding,
+            BoundsPadding::ViewportFraction(fraction) if fraction < 0.5 => {
+                bounded_area_size / (1. - 2. * fraction)
+            }
+            BoundsPadding::ViewportFraction(_) => Vec2::INFINITY,
+        }
+    }
+}
+
+impl Default for BoundsPadding {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
 impl Plugin for PanCamPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (do_camera_movement, do_camera_zoom).in_set(PanCamSystems),
-        )
-        .add_observer(on_clamp_bounds)
-        .register_type::<PanCam>()
-        .register_type::<DirectionKeys>();
+        app.init_resource::<TouchWindows>()
+            .configure_sets(Update, PanCamInputSystems.in_set(PanCamSystems))
+            .add_systems(
+                Update,
+                (
+                    clamp_changed_bounds
+                        .before(rotation::do_camera_rotation)
+                        .before(do_camera_movement)
+                        .before(do_camera_zoom),
+                    touch::track_touch_windows
+                        .before(do_camera_movement)
+                        .before(do_camera_zoom)
+                        .before(rotation::do_camera_rotation),
+                    (
+                        rotation::do_camera_rotation
+                            .before(do_camera_movement)
+                            .before(do_camera_zoom),
+                        do_camera_movement,
+                        do_camera_zoom,
+                    )
+                        .in_set(PanCamInputSystems),
+                    fit::do_camera_fit.before(fly_to::do_camera_fly_to),
+                    fly_to::do_camera_fly_to
+                        .after(do_camera_movement)
+                        .after(do_camera_zoom),
+                    follow::do_camera_follow
+                        .after(do_camera_movement)
+                        .after(do_camera_zoom)
+                        .before(fly_to::do_camera_fly_to),
+                    wrap::wrap_cameras
+                        .after(do_camera_movement)
+                        .after(do_camera_zoom)
+                        .after(fly_to::do_camera_fly_to)
+                        .after(follow::do_camera_follow),
+                    state::update_pan_cam_state.after(wrap::wrap_cameras),
+                )
+                    .in_set(PanCamSystems),
+            )
+            .add_observer(on_clamp_bounds)
+            .add_observer(fly_to::on_fly_to)
+            .add_observer(fit::on_fit_rect)
+            .add_observer(fit::on_fit_entities)
+            .register_type::<PanCam>()
+            .register_type::<DirectionKeys>()
+            .register_type::<ZoomKeys>()
+            .register_type::<Momentum>()
+            .register_type::<ElasticBounds>()
+            .register_type::<EdgeScroll>()
+            .register_type::<GamepadControls>()
+            .register_type::<RotationControls>()
+            .register_type::<ScrollBehavior>()
+            .register_type::<WheelZoom>()
+            .register_type::<SmallBounds>()
+            .register_type::<BoundsPadding>()
+            .register_type::<KeepInside>()
+            .register_type::<CircleBounds>()
+            .register_type::<PanCamFollow>()
+            .register_type::<PanCamState>()
+            .register_type::<BoundsContact>();
 
         #[cfg(feature = "internal_bevy_egui")]
         app.add_plugins(egui_support::EguiPanCamPlugin);
@@ -124,22 +425,58 @@ impl Plugin for PanCamPlugin {
 }
 
 fn do_camera_zoom(
-    mut query: Query<(&PanCam, &Camera, &mut Projection, &mut Transform)>,
-    pinch_events: MessageReader<PinchGesture>,
-    scroll_events: MessageReader<MouseWheel>,
-    primary_window: Query<&Window, With<PrimaryWindow>>,
+    mut query: Query<(
+        Entity,
+        &PanCam,
+        &mut ZoomState,
+        &Camera,
+        &RenderTarget,
+        &mut Projection,
+        &mut Transform,
+    )>,
+    mut snap_states: Query<&mut SnapState>,
+    inputs: PanCamInputs,
+    windows: PanCamWindows,
+    mut commands: Commands,
+    time: Res<Time<Real>>,
 ) {
+    let PanCamInputs {
+        keyboard_buttons,
+        touch_inputs,
+        gamepads,
+        scroll_events,
+        pinch_events,
+        ..
+    } = inputs;
+
     let zoom_inputs = NormalizedZoomInputs::from_events(scroll_events, pinch_events);
-    if zoom_inputs.is_empty() {
-        return;
-    }
+    let primary_window = windows.primary();
 
-    let Ok(window) = primary_window.single() else {
-        return;
-    };
+    // A touch pinch goes to the camera under the point between the fingers
+    let touch_pinch = touch_inputs.routed_pinch(
+        primary_window,
+        query
+            .iter()
+            .map(|(entity, pan_cam, _, camera, target, ..)| (entity, pan_cam, camera, target)),
+    );
+
+    // Zoom input goes to the camera under the cursor, or to all of them when
+    // there is no cursor to go by
+    let cursor_positions = windows.cursor_positions();
+    let hovered_cameras = hovered_cameras(
+        &cursor_positions,
+        primary_window,
+        query
+            .iter()
+            .map(|(entity, pan_cam, _, camera, target, ..)| (entity, pan_cam, camera, target)),
+    );
+    let has_cursor = !cursor_positions.is_empty();
 
-    for (pan_cam, camera, mut proj, mut transform) in &mut query {
+    for (entity, pan_cam, mut zoom_state, camera, render_target, mut proj, mut transform) in
+        &mut query
+    {
         if !pan_cam.enabled {
+            zoom_state.target_scale = None;
             continue;
         }
 
@@ -148,125 +485,408 @@ fn do_camera_zoom(
             _ => continue,
         };
 
+        let Some((_, window)) = windows.camera_window(render_target) else {
+            continue;
+        };
+
         let view_size = camera.logical_viewport_size().unwrap_or(window.size());
 
         let prev_scale = proj.scale;
-        let zoom_delta = zoom_inputs.apply_sensitivity(
-            pan_cam.mouse_wheel_sensitivity,
-            pan_cam.pinch_gesture_sensitivity,
-        );
-        proj.scale *= 1. - zoom_delta;
+        let zoom_inputs =
+            zoom_inputs.with_scroll_behavior(pan_cam.scroll_behavior, &keyboard_buttons);
+        let has_zoom_input = !zoom_inputs.is_empty()
+            && (!has_cursor || hovered_cameras.values().any(|hovered| *hovered == entity));
+        let touch_pinch = touch_pinch
+            .filter(|(_, camera)| pan_cam.touch_controls && *camera == entity)
+            .map(|(pinch, _)| pinch);
+
+        // With zoom steps, wheel notches and key presses move between levels,
+        // while pinching and gamepads zoom continuously and settle on the
+        // nearest level once they stop
+        let zoom_steps = pan_cam
+            .zoom_steps
+            .as_deref()
+            .filter(|levels| !levels.is_empty());
+
+        let mut scale_factor = 1.;
+        let mut pointer_steps = 0;
+        if has_zoom_input {
+            if zoom_steps.is_some() {
+                zoom_state.wheel_steps += zoom_inputs.wheel_steps(&pan_cam.wheel_zoom);
+                pointer_steps = zoom_state.wheel_steps.trunc() as i32;
+                zoom_state.wheel_steps = zoom_state.wheel_steps.fract();
+                scale_factor *= 1. - zoom_inputs.pinch * pan_cam.pinch_gesture_sensitivity;
+            } else {
+                scale_factor *= 1.
+                    - zoom_inputs.apply_sensitivity(
+                        &pan_cam.wheel_zoom,
+                        pan_cam.mouse_wheel_sensitivity,
+                        pan_cam.pinch_gesture_sensitivity,
+                    );
+            }
+        }
+        if let Some(touch_pinch) = touch_pinch {
+            scale_factor *= touch_pinch.scale_factor;
+        }
+        let has_pointer_zoom = scale_factor != 1. || pointer_steps != 0;
 
-        constrain_proj_scale(
-            proj,
-            pan_cam.rect().size(),
-            &pan_cam.scale_range(),
-            view_size,
-        );
+        // Keyboard and gamepad zoom have no position of their own
+        let mut centered_scale_factor = 1.;
+        let mut centered_steps = 0;
+        if zoom_steps.is_some() {
+            centered_steps = pan_cam.zoom_keys.just_pressed_direction(&keyboard_buttons);
+        } else {
+            let zoom_key_direction = pan_cam.zoom_keys.direction(&keyboard_buttons);
+            centered_scale_factor *= pan_cam
+                .zoom_speed
+                .powf(-zoom_key_direction * time.delta_secs());
+        }
+        if let Some(controls) = &pan_cam.gamepad {
+            centered_scale_factor *= gamepads.scale_factor(controls, time.delta_secs());
+        }
+        scale_factor *= centered_scale_factor;
+        let has_centered_zoom = centered_scale_factor != 1. || centered_steps != 0;
+        let mut centered_only = has_centered_zoom && !has_pointer_zoom;
+
+        // Accumulate input on the target, so fast consecutive wheel notches
+        // add up instead of restarting from the current scale
+        let base_scale = zoom_state.target_scale.unwrap_or(proj.scale);
+        let mut desired_scale = (scale_factor != 1.).then_some(base_scale * scale_factor);
+
+        if let Some(levels) = zoom_steps
+            && let Some((step_scale, centered)) = step_zoom(
+                levels,
+                &mut zoom_state.continuous_zoom,
+                ContinuousZoom {
+                    last_input: time.elapsed_secs(),
+                    touch: touch_pinch.is_some(),
+                    centered: centered_only,
+                },
+                base_scale,
+                scale_factor,
+                pointer_steps + centered_steps,
+            )
+        {
+            desired_scale = Some(step_scale);
+            centered_only = centered;
+        }
 
-        let cursor_normalized_viewport_pos = window
-            .cursor_position()
-            .map(|cursor_pos| {
-                let view_pos = camera
-                    .logical_viewport_rect()
-                    .map(|v| v.min)
-                    .unwrap_or(Vec2::ZERO);
+        // Zoom around where the cursor, or the point between pinching fingers,
+        // was, keeping that world position under where it is now. Keyboard and
+        // gamepad zoom on their own zoom towards the middle. Smoothing keeps
+        // zooming around the same point once the input stops.
+        let anchor = match touch_pinch {
+            Some(touch_pinch) => Some((touch_pinch.previous_midpoint, touch_pinch.midpoint)),
+            None if desired_scale.is_none() => zoom_state.anchor.map(|anchor| (anchor, anchor)),
+            None if centered_only => None,
+            None => window
+                .cursor_position()
+                .map(|cursor_pos| (cursor_pos, cursor_pos)),
+        };
+        zoom_state.anchor = anchor.map(|(_, anchor)| anchor);
+
+        if let Some(smoothing) = pan_cam.zoom_smoothing {
+            if let Some(desired_scale) = desired_scale {
+                let mut target_proj = proj.clone();
+                target_proj.scale = desired_scale;
+                constrain_proj_scale(&mut target_proj, pan_cam, view_size, transform.rotation);
+                zoom_state.target_scale = Some(target_proj.scale);
+            }
+
+            let Some(target_scale) = zoom_state.target_scale else {
+                continue;
+            };
+
+            proj.scale
+                .smooth_nudge(&target_scale, smoothing, time.delta_secs());
+
+            if (proj.scale - target_scale).abs() <= target_scale * 0.001 {
+                proj.scale = target_scale;
+                zoom_state.target_scale = None;
+            }
+        } else {
+            zoom_state.target_scale = None;
+            let Some(desired_scale) = desired_scale else {
+                continue;
+            };
+            proj.scale = desired_scale;
+        }
 
-                ((cursor_pos - view_pos) / view_size) * 2. - Vec2::ONE
-            })
-            .map(|p| vec2(p.x, -p.y));
+        constrain_proj_scale(proj, pan_cam, view_size, transform.rotation);
 
-        // Move the camera position to normalize the projection window
-        let (Some(cursor_normalized_view_pos), true) =
-            (cursor_normalized_viewport_pos, pan_cam.zoom_to_cursor)
-        else {
-            continue;
-        };
+        if proj.scale != prev_scale {
+            commands.trigger(PanCamZoomed {
+                entity,
+                old_scale: prev_scale,
+                new_scale: proj.scale,
+            });
+        }
 
         let proj_size = proj.area.max / prev_scale;
+        let area_size = proj.area.size() / prev_scale * proj.scale;
 
-        let cursor_world_pos =
-            transform.translation.truncate() + cursor_normalized_view_pos * proj_size * prev_scale;
+        let Ok(mut snap_state) = snap_states.get_mut(entity) else {
+            continue;
+        };
+        let cam_pos = snap_state.position(transform.translation.truncate());
 
-        let proposed_cam_pos =
-            cursor_world_pos - cursor_normalized_view_pos * proj_size * proj.scale;
+        // Move the camera position to normalize the projection window
+        let proposed_cam_pos = match (anchor, pan_cam.zoom_to_cursor) {
+            (Some((previous_anchor, anchor)), true) => {
+                let view_pos = camera
+                    .logical_viewport_rect()
+                    .map(|v| v.min)
+                    .unwrap_or(Vec2::ZERO);
+                let normalized_view_pos = |pos: Vec2| {
+                    let p = ((pos - view_pos) / view_size) * 2. - Vec2::ONE;
+                    vec2(p.x, -p.y)
+                };
+
+                // Offsets from the middle of the view are along the rotated
+                // view axes
+                let to_world = |offset: Vec2| (transform.rotation * offset.extend(0.)).truncate();
+
+                let cursor_world_pos = cam_pos
+                    + to_world(normalized_view_pos(previous_anchor) * proj_size * prev_scale);
+                cursor_world_pos - to_world(normalized_view_pos(anchor) * proj_size * proj.scale)
+            }
+            // Zooming out around the middle may still show more than the bounds
+            _ => cam_pos,
+        };
 
         // As we zoom out, we don't want the viewport to move beyond the provided
         // boundary. If the most recent change to the camera zoom would move cause
         // parts of the window beyond the boundary to be shown, we need to change the
         // camera position to keep the viewport within bounds.
-        transform.translation =
-            clamp_to_safe_zone(proposed_cam_pos, pan_cam.aabb(), proj.area.size())
-                .extend(transform.translation.z);
+        let new_cam_pos = clamp_to_safe_zone(
+            proposed_cam_pos,
+            pan_cam,
+            view_extent(area_size, transform.rotation),
+        );
+        if new_cam_pos != proposed_cam_pos {
+            commands.trigger(PanCamHitBounds {
+                entity,
+                proposed_translation: proposed_cam_pos,
+                translation: new_cam_pos,
+            });
+        }
+
+        let pixel_size = area_size / view_size / window.scale_factor();
+        let previous_translation = transform.translation.truncate();
+        transform.translation = snap_state
+            .apply(new_cam_pos, pan_cam, pixel_size, transform.rotation)
+            .extend(transform.translation.z);
+        trigger_moved(&mut commands, entity, previous_translation, &transform);
+    }
+}
+
+/// Triggers [`PanCamMoved`] if the camera translation changed
+fn trigger_moved(
+    commands: &mut Commands,
+    entity: Entity,
+    previous_translation: Vec2,
+    transform: &Transform,
+) {
+    let translation = transform.translation.truncate();
+    if translation != previous_translation {
+        commands.trigger(PanCamMoved {
+            entity,
+            previous_translation,
+            translation,
+        });
     }
 }
 
 /// `max_scale_within_bounds` is used to find the maximum safe zoom out/projection
 /// scale when we have been provided with minimum and maximum x boundaries for
-/// the camera.
+/// the camera. A rotated view covers more of the bounds than an upright one.
 fn max_scale_within_bounds(
     bounded_area_size: Vec2,
+    padding: BoundsPadding,
     proj: &OrthographicProjection,
     window_size: Vec2, //viewport?
+    rotation: Quat,
 ) -> Vec2 {
+    padding.max_view_size(bounded_area_size)
+        / view_extent(base_world_size(proj, window_size), rotation)
+}
+
+/// `base_world_size` is the size of the world area shown at a projection scale
+/// of 1.
+fn base_world_size(proj: &OrthographicProjection, window_size: Vec2) -> Vec2 {
     let mut proj = proj.clone();
     proj.scale = 1.;
     proj.update(window_size.x, window_size.y);
-    let base_world_size = proj.area.size();
-    bounded_area_size / base_world_size
+    proj.area.size()
+}
+
+/// `view_extent` is the size of the world aligned box around a view of the
+/// given size when the camera is rotated.
+fn view_extent(view_size: Vec2, rotation: Quat) -> Vec2 {
+    (Mat3::from_quat(rotation).abs() * view_size.extend(0.)).truncate()
+}
+
+/// `scale_to_fit` finds the projection scale at which an area of the given size
+/// fits the viewport, leaving `padding` logical pixels free around it.
+fn scale_to_fit(size: Vec2, proj: &OrthographicProjection, window_size: Vec2, padding: f32) -> f32 {
+    // Never let the padding take up all of the viewport
+    let unpadded_fraction = (Vec2::ONE - 2. * padding / window_size).max(Vec2::splat(0.01));
+    max_scale_within_bounds(
+        size / unpadded_fraction,
+        BoundsPadding::NONE,
+        proj,
+        window_size,
+        Quat::IDENTITY,
+    )
+    .max_element()
 }
 
-/// Makes sure that the camera projection scale stays in the provided bounds
-/// and range.
+/// Makes sure that the camera projection scale stays in the scale range of
+/// the camera, and doesn't show more than its bounds.
 fn constrain_proj_scale(
     proj: &mut OrthographicProjection,
-    bounded_area_size: Vec2,
-    scale_range: &RangeInclusive<f32>,
+    pan_cam: &PanCam,
     window_size: Vec2,
+    rotation: Quat,
 ) {
-    proj.scale = proj.scale.clamp(*scale_range.start(), *scale_range.end());
+    proj.scale = proj.scale.clamp(pan_cam.min_scale, pan_cam.max_scale);
 
     // If there is both a min and max boundary, that limits how far we can zoom.
-    // Make sure we don't exceed that
+    // Make sure we don't exceed that.
+    let bounded_area_size = pan_cam.zoom_bounds_size();
     if bounded_area_size.x.is_finite() || bounded_area_size.y.is_finite() {
-        let max_safe_scale = max_scale_within_bounds(bounded_area_size, proj, window_size);
+        let max_safe_scale = max_scale_within_bounds(
+            bounded_area_size,
+            pan_cam.bounds_padding,
+            proj,
+            window_size,
+            rotation,
+        );
         proj.scale = proj.scale.min(max_safe_scale.x).min(max_safe_scale.y);
     }
+
+    // A wrapping world may never be shown twice, whatever the padding
+    let wrap_size = pan_cam.wrap_size();
+    if wrap_size.x.is_finite() || wrap_size.y.is_finite() {
+        let max_wrap_scale =
+            max_scale_within_bounds(wrap_size, BoundsPadding::NONE, proj, window_size, rotation);
+        proj.scale = proj.scale.min(max_wrap_scale.x).min(max_wrap_scale.y);
+    }
 }
 
 /// Clamps a camera position to a safe zone. "Safe" means that each screen
 /// corner is constrained to the corresponding bound corner.
 ///
-/// Since bevy doesn't provide a `shrink` method on a `Rect` yet, we have to
-/// operate on `Aabb2d` type.
-fn clamp_to_safe_zone(pos: Vec2, aabb: Aabb2d, bounded_area_size: Vec2) -> Vec2 {
-    let aabb = aabb.shrink(bounded_area_size / 2.);
-    pos.clamp(aabb.min, aabb.max)
+/// On axes where the view is bigger than the bounds there is no such position,
+/// so the camera is placed according to [`PanCam::small_bounds_x`] and
+/// [`PanCam::small_bounds_y`]. [`PanCam::shape_bounds`] are applied last, and
+/// [`PanCam::bounds_padding`] lets the view go past both. Wrapping axes aren't
+/// clamped.
+fn clamp_to_safe_zone(pos: Vec2, pan_cam: &PanCam, bounded_area_size: Vec2) -> Vec2 {
+    // The padding may scroll past the bounds
+    let bounded_area_size = pan_cam.bounds_padding.inner_view_size(bounded_area_size);
+    let (min, max) = safe_zone(pan_cam.aabb(), bounded_area_size);
+    // Wrapping axes are brought back within the bounds by teleporting instead,
+    // see `wrap::wrap_cameras`
+    let pos = vec2(
+        if pan_cam.wrap_x {
+            pos.x
+        } else {
+            clamp_axis(pos.x, min.x, max.x, pan_cam.small_bounds_x)
+        },
+        if pan_cam.wrap_y {
+            pos.y
+        } else {
+            clamp_axis(pos.y, min.y, max.y, pan_cam.small_bounds_y)
+        },
+    );
+    match &pan_cam.shape_bounds {
+        Some(shape_bounds) => shape_bounds.clamp(pos, bounded_area_size),
+        None => pos,
+    }
+}
+
+/// Returns the (min, max) camera positions that keep a view of the given size
+/// within the bounds. The min is bigger than the max on axes where the view is
+/// bigger than the bounds.
+///
+/// Unlike `Aabb2d::shrink`, this doesn't assert that the result is valid.
+fn safe_zone(aabb: Aabb2d, bounded_area_size: Vec2) -> (Vec2, Vec2) {
+    let half_size = bounded_area_size / 2.;
+    (aabb.min + half_size, aabb.max - half_size)
+}
+
+/// Clamps one axis of a camera position to the safe range `min..=max`, which
+/// is inverted when the view is bigger than the bounds.
+fn clamp_axis(pos: f32, min: f32, max: f32, small_bounds: SmallBounds) -> f32 {
+    if min <= max {
+        return pos.clamp(min, max);
+    }
+
+    // `min` puts the view's min edge on the min bound, and `max` its max edge
+    // on the max bound
+    match small_bounds {
+        SmallBounds::ZoomIn | SmallBounds::Center => (min + max) / 2.,
+        SmallBounds::AlignMin => min,
+        SmallBounds::AlignMax => max,
+        SmallBounds::Free => pos.clamp(max, min),
+    }
 }
 
 fn do_camera_movement(
-    primary_window: Query<&Window, With<PrimaryWindow>>,
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
-    keyboard_buttons: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&PanCam, &Camera, &mut Transform, &Projection)>,
-    mut last_pos: Local<Option<Vec2>>,
+    windows: PanCamWindows,
+    inputs: PanCamInputs,
+    mut query: Query<(
+        Entity,
+        &PanCam,
+        &mut DragState,
+        &Camera,
+        &RenderTarget,
+        &mut Transform,
+        &Projection,
+    )>,
+    mut snap_states: Query<&mut SnapState>,
+    mut commands: Commands,
     time: Res<Time<Real>>,
 ) {
-    let Ok(window) = primary_window.single() else {
-        return;
-    };
-    let window_size = window.size();
+    let PanCamInputs {
+        mouse_buttons,
+        keyboard_buttons,
+        touch_inputs,
+        gamepads,
+        scroll_events,
+        ..
+    } = inputs;
+
+    let primary_window = windows.primary();
+
+    let cursor_positions = windows.cursor_positions();
+    let pixel_scroll = pixel_scroll(scroll_events);
+
+    // A single finger touching down grabs the camera under it
+    let touch_grab = touch_inputs.grab(
+        primary_window,
+        query
+            .iter()
+            .map(|(entity, pan_cam, _, camera, target, ..)| (entity, pan_cam, camera, target)),
+    );
 
-    // Use position instead of MouseMotion, otherwise we don't get acceleration
-    // movement
-    let current_pos = match window.cursor_position() {
-        Some(c) => vec2(c.x, -c.y),
-        None => return,
-    };
-    let delta_device_pixels = current_pos - last_pos.unwrap_or(current_pos);
+    let hovered_cameras = hovered_cameras(
+        &cursor_positions,
+        primary_window,
+        query
+            .iter()
+            .map(|(entity, pan_cam, _, camera, target, ..)| (entity, pan_cam, camera, target)),
+    );
 
-    for (pan_cam, camera, mut transform, projection) in &mut query {
+    for (entity, pan_cam, mut drag_state, camera, render_target, mut transform, projection) in
+        &mut query
+    {
         if !pan_cam.enabled {
+            if drag_state.grabbed {
+                commands.trigger(PanCamDragEnded { entity });
+            }
+            drag_state.stop();
             continue;
         }
 
@@ -275,69 +895,582 @@ fn do_camera_movement(
             _ => continue,
         };
 
+        let Some((window_entity, window)) = windows.camera_window(render_target) else {
+            continue;
+        };
+
+        // Use position instead of MouseMotion, otherwise we don't get
+        // acceleration movement
+        let delta_device_pixels =
+            drag_state.cursor_delta(window_entity, cursor_positions.get(&window_entity).copied());
+
         let proj_area_size = projection.area.size();
+        let bounded_view_size = view_extent(proj_area_size, transform.rotation);
+        // Screen directions are along the rotated view axes
+        let to_world = |delta: Vec2| (transform.rotation * delta.extend(0.)).truncate();
 
-        let viewport_size = camera.logical_viewport_size().unwrap_or(window_size);
+        let viewport_size = camera.logical_viewport_size().unwrap_or(window.size());
         let world_units_per_pixel = proj_area_size / viewport_size;
 
-        let mouse_delta = if !pan_cam
-            .grab_buttons
-            .iter()
-            .any(|btn| mouse_buttons.pressed(*btn) && !mouse_buttons.just_pressed(*btn))
+        // A drag belongs to the camera it started on until it's released
+        let was_grabbed = drag_state.grabbed;
+        let drag_pixels = if drag_state.touch_id.is_some() {
+            touch_inputs.drag(&mut drag_state)
+        } else if pan_cam.touch_controls
+            && !drag_state.grabbed
+            && let Some((touch_id, _)) = touch_grab.filter(|(_, camera)| *camera == entity)
         {
-            Vec2::ZERO
+            drag_state.touch_id = Some(touch_id);
+            drag_state.grabbed = true;
+            drag_state.grab_button = None;
+            None
         } else {
-            delta_device_pixels * world_units_per_pixel
+            mouse_drag(
+                pan_cam,
+                &mut drag_state,
+                hovered_cameras.get(&window_entity) == Some(&entity),
+                &mouse_buttons,
+                &keyboard_buttons,
+                delta_device_pixels,
+            )
         };
+        let dragging = drag_pixels.is_some();
+        let drag_delta = drag_pixels.map(|pixels| to_world(pixels * world_units_per_pixel));
 
-        let direction = pan_cam.move_keys.direction(&keyboard_buttons);
+        match (was_grabbed, drag_state.grabbed) {
+            (false, true) => commands.trigger(PanCamDragStarted { entity }),
+            (true, false) => commands.trigger(PanCamDragEnded { entity }),
+            _ => {}
+        }
 
-        let keyboard_delta = time.delta_secs()
-            * direction.normalize_or_zero()
-            * pan_cam.speed
-            * world_units_per_pixel;
-        let delta = mouse_delta - keyboard_delta;
+        // Analog sticks give partial speeds, keys always move at full speed
+        let mut direction = pan_cam
+            .move_keys
+            .direction(&keyboard_buttons)
+            .normalize_or_zero();
+        if let Some(controls) = &pan_cam.gamepad {
+            direction = (direction + gamepads.direction(controls)).clamp_length_max(1.);
+        }
 
-        if delta == Vec2::ZERO {
-            continue;
+        let keyboard_delta =
+            to_world(time.delta_secs() * direction * pan_cam.speed * world_units_per_pixel);
+        let mut delta = drag_delta.unwrap_or_default() - keyboard_delta;
+
+        // Like zooming, scrolling goes to the camera under the cursor. The
+        // content follows the fingers on the trackpad, just like dragging.
+        if pixel_scroll != Vec2::ZERO
+            && pixel_scroll_pans(pan_cam.scroll_behavior, &keyboard_buttons)
+            && (cursor_positions.is_empty() || hovered_cameras.get(&window_entity) == Some(&entity))
+        {
+            delta += to_world(vec2(pixel_scroll.x, -pixel_scroll.y) * world_units_per_pixel);
+        }
+
+        if let Some(edge_scroll) = &pan_cam.edge_scroll
+            && let Some(cursor_pos) = cursor_positions.get(&window_entity)
+            && hovered_cameras.get(&window_entity) == Some(&entity)
+            && !drag_state.grabbed
+        {
+            let viewport = camera
+                .logical_viewport_rect()
+                .unwrap_or(Rect::from_corners(Vec2::ZERO, window.size()));
+            let edge_pixels =
+                edge_scroll.scroll(*cursor_pos, viewport, window.focused, time.delta_secs());
+            delta -= to_world(edge_pixels * world_units_per_pixel);
         }
 
+        // Gliding may carry an elastic camera past the bounds, unlike input
+        let glide_delta = pan_cam.momentum.as_ref().map_or(Vec2::ZERO, |momentum| {
+            momentum.glide(
+                &mut drag_state,
+                was_grabbed,
+                drag_delta,
+                &time,
+                world_units_per_pixel,
+            )
+        });
+
+        // Only count input that moves the camera, so e.g. clicking doesn't
+        // pause following
+        if delta != Vec2::ZERO || glide_delta != Vec2::ZERO {
+            drag_state.last_user_pan = Some(time.elapsed_secs());
+        }
+
+        let Ok(mut snap_state) = snap_states.get_mut(entity) else {
+            continue;
+        };
+        let cam_pos = snap_state.position(transform.translation.truncate());
         // The proposed new camera position
-        let proposed_cam_pos = transform.translation.truncate() - delta;
+        let proposed_cam_pos = cam_pos - delta - glide_delta;
+        // Where input would have moved the camera had the bounds not stopped
+        // it, if they did
+        let mut blocked_cam_pos = proposed_cam_pos;
+
+        let new_cam_pos = match &pan_cam.elastic_bounds {
+            Some(elastic_bounds) if dragging => elastic_bounds.drag(
+                &mut drag_state.unresisted_pos,
+                cam_pos,
+                delta,
+                pan_cam,
+                bounded_view_size,
+                world_units_per_pixel,
+            ),
+            Some(elastic_bounds) => {
+                drag_state.unresisted_pos = None;
+                let (new_cam_pos, proposed_cam_pos) = elastic_bounds.spring_back(
+                    cam_pos - glide_delta,
+                    delta,
+                    pan_cam,
+                    bounded_view_size,
+                    world_units_per_pixel,
+                    time.delta_secs(),
+                );
+                blocked_cam_pos = proposed_cam_pos;
+                new_cam_pos
+            }
+            None => clamp_to_safe_zone(proposed_cam_pos, pan_cam, bounded_view_size),
+        };
+
+        // Elastic bounds resist rather than stop a drag
+        if !(dragging && pan_cam.elastic_bounds.is_some()) && new_cam_pos != blocked_cam_pos {
+            commands.trigger(PanCamHitBounds {
+                entity,
+                proposed_translation: blocked_cam_pos,
+                translation: new_cam_pos,
+            });
+        }
+
+        if new_cam_pos == cam_pos {
+            continue;
+        }
 
-        transform.translation =
-            clamp_to_safe_zone(proposed_cam_pos, pan_cam.aabb(), proj_area_size)
-                .extend(transform.translation.z);
+        // Stop gliding along an axis once the camera hits the bounds
+        if new_cam_pos.x != proposed_cam_pos.x {
+            drag_state.velocity.x = 0.;
+        }
+        if new_cam_pos.y != proposed_cam_pos.y {
+            drag_state.velocity.y = 0.;
+        }
+
+        let pixel_size = world_units_per_pixel / window.scale_factor();
+        let previous_translation = transform.translation.truncate();
+        transform.translation = snap_state
+            .apply(new_cam_pos, pan_cam, pixel_size, transform.rotation)
+            .extend(transform.translation.z);
+        trigger_moved(&mut commands, entity, previous_translation, &transform);
     }
-    *last_pos = Some(current_pos);
+}
+
+/// Grabs and releases a camera with the mouse, returning how far the cursor
+/// dragged it this frame in logical pixels with y growing upwards
+///
+/// The drag lasts until the button that started it is released, even if the
+/// cursor leaves the window in between.
+fn mouse_drag(
+    pan_cam: &PanCam,
+    drag_state: &mut DragState,
+    hovered: bool,
+    mouse_buttons: &ButtonInput<MouseButton>,
+    keyboard_buttons: &ButtonInput<KeyCode>,
+    cursor_delta: Vec2,
+) -> Option<Vec2> {
+    if let Some(button) = drag_state.grab_button {
+        if !mouse_buttons.pressed(button) {
+            drag_state.grabbed = false;
+            drag_state.grab_button = None;
+        }
+    } else if hovered
+        && let Some(button) = pan_cam
+            .grab_buttons
+            .iter()
+            .find(|btn| mouse_buttons.just_pressed(**btn))
+    {
+        drag_state.grabbed = true;
+        drag_state.grab_button = Some(*button);
+    }
+
+    // Dragging with a rotation modifier held rotates the camera instead, see
+    // `do_camera_rotation`
+    let rotating = pan_cam
+        .rotation
+        .as_ref()
+        .is_some_and(|controls| controls.drag_rotates(keyboard_buttons));
+
+    drag_state
+        .grab_button
+        .filter(|button| !rotating && !mouse_buttons.just_pressed(*button))
+        .map(|_| cursor_delta)
+}
+
+/// Returns the window entity whose input drives a camera
+///
+/// Cameras that don't render to a window, e.g. to an image shown somewhere
+/// in the UI, are driven by the primary window.
+fn camera_window(render_target: &RenderTarget, primary_window: Option<Entity>) -> Option<Entity> {
+    match render_target {
+        RenderTarget::Window(window_ref) => window_ref
+            .normalize(primary_window)
+            .map(|window_ref| window_ref.entity()),
+        _ => primary_window,
+    }
+}
+
+/// The input devices that control `PanCam` cameras
+#[derive(SystemParam)]
+struct PanCamInputs<'w, 's> {
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    keyboard_buttons: Res<'w, ButtonInput<KeyCode>>,
+    touch_inputs: TouchInputs<'w>,
+    gamepads: Gamepads<'w, 's>,
+    scroll_events: MessageReader<'w, 's, MouseWheel>,
+    pinch_events: MessageReader<'w, 's, PinchGesture>,
+    rotation_events: MessageReader<'w, 's, RotationGesture>,
+}
+
+/// The windows whose input drives `PanCam` cameras
+#[derive(SystemParam)]
+struct PanCamWindows<'w, 's> {
+    windows: Query<'w, 's, (Entity, &'static Window)>,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+}
+
+impl PanCamWindows<'_, '_> {
+    fn primary(&self) -> Option<Entity> {
+        self.primary_window.single().ok()
+    }
+
+    /// Returns the window whose input drives a camera, see [`camera_window`]
+    fn camera_window(&self, render_target: &RenderTarget) -> Option<(Entity, &Window)> {
+        camera_window(render_target, self.primary())
+            .and_then(|window_entity| self.windows.get(window_entity).ok())
+    }
+
+    /// Returns the logical cursor position of every window the cursor is in
+    fn cursor_positions(&self) -> HashMap<Entity, Vec2> {
+        self.windows
+            .iter()
+            .filter_map(|(entity, window)| window.cursor_position().map(|pos| (entity, pos)))
+            .collect()
+    }
+}
+
+/// Finds the enabled camera whose viewport is under the cursor for each window,
+/// preferring the camera that renders last where viewports overlap
+fn hovered_cameras<'a>(
+    cursor_positions: &HashMap<Entity, Vec2>,
+    primary_window: Option<Entity>,
+    cameras: impl Iterator<Item = (Entity, &'a PanCam, &'a Camera, &'a RenderTarget)>,
+) -> HashMap<Entity, Entity> {
+    let mut hovered = HashMap::<Entity, (isize, Entity)>::default();
+
+    for (entity, pan_cam, camera, render_target) in cameras {
+        if !pan_cam.enabled || !camera.is_active {
+            continue;
+        }
+
+        let Some(window_entity) = camera_window(render_target, primary_window) else {
+            continue;
+        };
+
+        let Some(cursor_pos) = cursor_positions.get(&window_entity) else {
+            continue;
+        };
+
+        if camera
+            .logical_viewport_rect()
+            .is_some_and(|rect| !rect.contains(*cursor_pos))
+        {
+            continue;
+        }
+
+        let topmost = hovered
+            .entry(window_entity)
+            .or_insert((camera.order, entity));
+        if camera.order > topmost.0 {
+            *topmost = (camera.order, entity);
+        }
+    }
+
+    hovered
+        .into_iter()
+        .map(|(window_entity, (_, entity))| (window_entity, entity))
+        .collect()
 }
 
 fn on_clamp_bounds(
     event: On<PanCamClampBounds>,
-    mut query: Query<(&PanCam, &mut Transform, &Projection)>,
+    mut query: Query<(
+        &PanCam,
+        &mut SnapState,
+        &Camera,
+        &RenderTarget,
+        &mut Transform,
+        &mut Projection,
+    )>,
+    windows: PanCamWindows,
+    mut commands: Commands,
 ) {
-    if let Ok((pan_cam, mut transform, Projection::Orthographic(projection))) =
-        query.get_mut(event.observer())
-    {
-        if !pan_cam.enabled {
-            return;
+    let entity = event.entity;
+    let Ok((pan_cam, mut snap_state, camera, render_target, mut transform, mut projection)) =
+        query.get_mut(entity)
+    else {
+        warn_once!("Tried to clamp bounds for an unsupported entity.");
+        return;
+    };
+    let Projection::Orthographic(projection) = &mut *projection else {
+        warn_once!("Tried to clamp bounds for an unsupported entity.");
+        return;
+    };
+
+    if !pan_cam.enabled {
+        return;
+    }
+
+    let window = windows
+        .camera_window(render_target)
+        .map(|(_, window)| window);
+    let viewport_size = camera.logical_viewport_size().or(window.map(Window::size));
+
+    // The projection area is only refreshed after `PanCamSystems`, so work
+    // out the view size from the viewport when there is one
+    let area_size = match viewport_size {
+        Some(viewport_size) => {
+            let prev_scale = projection.scale;
+            constrain_proj_scale(projection, pan_cam, viewport_size, transform.rotation);
+            if projection.scale != prev_scale {
+                commands.trigger(PanCamZoomed {
+                    entity,
+                    old_scale: prev_scale,
+                    new_scale: projection.scale,
+                });
+            }
+            base_world_size(projection, viewport_size) * projection.scale
+        }
+        None => projection.area.size(),
+    };
+
+    let bounded_view_size = view_extent(area_size, transform.rotation);
+    let proposed_cam_pos = snap_state.position(transform.translation.truncate());
+    let new_cam_pos = clamp_to_safe_zone(proposed_cam_pos, pan_cam, bounded_view_size);
+    if new_cam_pos != proposed_cam_pos {
+        commands.trigger(PanCamHitBounds {
+            entity,
+            proposed_translation: proposed_cam_pos,
+            translation: new_cam_pos,
+        });
+    }
+
+    let scale_factor = window.map_or(1., Window::scale_factor);
+    let pixel_size = area_size / viewport_size.unwrap_or(Vec2::ONE) / scale_factor;
+
+    let previous_translation = transform.translation.truncate();
+    transform.translation = snap_state
+        .apply(new_cam_pos, pan_cam, pixel_size, transform.rotation)
+        .extend(transform.translation.z);
+    trigger_moved(&mut commands, entity, previous_translation, &transform);
+}
+
+/// Triggers [`PanCamClampBounds`] for cameras whose bounds, scale range,
+/// viewport or projection changed since the last frame, including when their
+/// window was resized
+///
+/// The values are compared rather than relying on change detection, since
+/// zooming touches the projection every frame.
+fn clamp_changed_bounds(
+    mut query: Query<(
+        Entity,
+        &PanCam,
+        &mut ClampState,
+        &Camera,
+        &RenderTarget,
+        &Projection,
+    )>,
+    windows: PanCamWindows,
+    mut commands: Commands,
+) {
+    for (entity, pan_cam, mut clamp_state, camera, render_target, projection) in &mut query {
+        let Projection::Orthographic(proj) = projection else {
+            continue;
+        };
+        let Some((_, window)) = windows.camera_window(render_target) else {
+            continue;
+        };
+
+        let viewport_size = camera.logical_viewport_size().unwrap_or(window.size());
+        let inputs = ClampInputs {
+            enabled: pan_cam.enabled,
+            bounds: pan_cam.rect(),
+            scale_range: (pan_cam.min_scale, pan_cam.max_scale),
+            small_bounds: (pan_cam.small_bounds_x, pan_cam.small_bounds_y),
+            padding: pan_cam.bounds_padding,
+            wrap: (pan_cam.wrap_x, pan_cam.wrap_y),
+            shape_bounds: pan_cam.shape_bounds.as_ref().map(|shape_bounds| {
+                (
+                    SameShape(shape_bounds.shape.clone()),
+                    shape_bounds.keep_inside,
+                )
+            }),
+            window_size: window.size(),
+            viewport_size,
+            base_view_size: base_world_size(proj, viewport_size),
+            // Zooming leaves the scale constrained and the camera within
+            // bounds, so this only re-clamps after game code sets the scale
+            scale: proj.scale,
+        };
+
+        if clamp_state.last.as_ref() != Some(&inputs) {
+            clamp_state.last = Some(inputs);
+            commands.trigger(PanCamClampBounds { entity });
         }
+    }
+}
 
-        let proj_area_size = projection.area.size();
-        let proposed_cam_pos = transform.translation.truncate();
+/// Per-camera state to re-clamp the camera when what it was clamped for
+/// changes, see [`clamp_changed_bounds`]
+#[derive(Component, Default)]
+struct ClampState {
+    last: Option<ClampInputs>,
+}
 
-        transform.translation =
-            clamp_to_safe_zone(proposed_cam_pos, pan_cam.aabb(), proj_area_size)
-                .extend(transform.translation.z);
-    } else {
-        warn_once!("Tried to clamp bounds for an unsupported entity.");
+/// Everything that decides how far a camera can zoom out and where it can go
+#[derive(Clone, PartialEq)]
+struct ClampInputs {
+    enabled: bool,
+    bounds: Rect,
+    scale_range: (f32, f32),
+    small_bounds: (SmallBounds, SmallBounds),
+    padding: BoundsPadding,
+    wrap: (bool, bool),
+    shape_bounds: Option<(SameShape, KeepInside)>,
+    window_size: Vec2,
+    viewport_size: Vec2,
+    /// The size of the view in world units at a projection scale of 1
+    base_view_size: Vec2,
+    scale: f32,
+}
+
+/// A shape that compares equal only to itself, as shapes can't change behind
+/// their `Arc`, only be replaced
+#[derive(Clone)]
+struct SameShape(Arc<dyn BoundsShape>);
+
+impl PartialEq for SameShape {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
     }
 }
 
+/// Per-camera state used to estimate the release velocity of a drag and to
+/// keep the camera gliding afterwards
+#[derive(Component, Default)]
+struct DragState {
+    /// Whether a drag started on this camera and hasn't been released yet
+    grabbed: bool,
+    /// The finger dragging the camera, if it's dragged by touch
+    touch_id: Option<u64>,
+    /// The mouse button that started the drag, if it's dragged by mouse
+    grab_button: Option<MouseButton>,
+    /// The window of the camera and the logical cursor position in it during
+    /// the previous frame, if the cursor was in the window
+    last_cursor: Option<(Entity, Vec2)>,
+    /// Recent drag samples as `(timestamp, delta time, camera displacement)`
+    samples: VecDeque<(f32, f32, Vec2)>,
+    /// Current glide velocity in world units per second
+    velocity: Vec2,
+    /// Where the current drag would have moved the camera if it wasn't for
+    /// the resistance of elastic bounds
+    unresisted_pos: Option<Vec2>,
+    /// When the user last panned the camera, in seconds since startup
+    last_user_pan: Option<f32>,
+}
+
+impl DragState {
+    /// Returns how far the cursor moved in the window of the camera since the
+    /// previous frame, in logical pixels with y growing upwards, and
+    /// remembers the current position
+    ///
+    /// The cursor re-entering the window doesn't count as movement, so a drag
+    /// that left the window picks up where the cursor comes back.
+    fn cursor_delta(&mut self, window: Entity, cursor_pos: Option<Vec2>) -> Vec2 {
+        let last_cursor = self.last_cursor.take();
+        let Some(cursor_pos) = cursor_pos else {
+            return Vec2::ZERO;
+        };
+        self.last_cursor = Some((window, cursor_pos));
+
+        match last_cursor {
+            Some((last_window, last_pos)) if last_window == window => {
+                let delta = cursor_pos - last_pos;
+                vec2(delta.x, -delta.y)
+            }
+            _ => Vec2::ZERO,
+        }
+    }
+
+    /// Records the camera displacement of a drag frame and forgets samples
+    /// older than `sample_window` seconds
+    fn push_sample(
+        &mut self,
+        timestamp: f32,
+        delta_secs: f32,
+        displacement: Vec2,
+        sample_window: f32,
+    ) {
+        self.samples
+            .push_back((timestamp, delta_secs, displacement));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(t, _, _)| *t < timestamp - sample_window)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Estimates the velocity from the recorded samples and clears them
+    fn release_velocity(&mut self) -> Vec2 {
+        let (duration, displacement) = self
+            .samples
+            .drain(..)
+            .fold((0., Vec2::ZERO), |(duration, displacement), (_, dt, d)| {
+                (duration + dt, displacement + d)
+            });
+
+        if duration > 0. {
+            displacement / duration
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    /// Releases any drag and stops any glide
+    fn stop(&mut self) {
+        self.grabbed = false;
+        self.touch_id = None;
+        self.grab_button = None;
+        self.samples.clear();
+        self.velocity = Vec2::ZERO;
+        self.unresisted_pos = None;
+    }
+}
+
+/// Per-camera state for smoothly interpolated zooming
+#[derive(Component, Default)]
+struct ZoomState {
+    /// The scale the projection is currently easing towards
+    target_scale: Option<f32>,
+    /// The window position smooth zooming eases around, or `None` when it
+    /// eases towards the middle of the view
+    anchor: Option<Vec2>,
+    /// Wheel scrolling that didn't add up to a whole zoom step yet
+    wheel_steps: f32,
+    /// Set while zoom steps are being skipped by pinching or a gamepad
+    continuous_zoom: Option<ContinuousZoom>,
+}
+
 /// A component that adds panning camera controls to an orthographic camera
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-#[require(Camera2d)]
+#[require(Camera2d, DragState, ZoomState, SnapState, ClampState, PanCamState)]
 pub struct PanCam {
     /// The mouse buttons that will be used to drag and pan the camera
     pub grab_buttons: Vec<MouseButton>,
@@ -348,6 +1481,16 @@ pub struct PanCam {
     /// Converted to world units using the camera's projected area and viewport
     /// size, so it works correctly with all scaling modes (including `AutoMin`).
     pub speed: f32,
+    /// The keyboard keys that will be used to zoom the camera
+    ///
+    /// No keys zoom by default, see [`ZoomKeys::plus_minus`] and the other
+    /// presets to enable keyboard zoom.
+    pub zoom_keys: ZoomKeys,
+    /// Keyboard zoom speed, as the factor the scale changes by per second
+    ///
+    /// `2.` halves or doubles the scale every second. Keyboard zoom is
+    /// limited by the scale range and bounds just like wheel zoom.
+    pub zoom_speed: f32,
     /// Whether camera currently responds to user input
     pub enabled: bool,
     /// When true, zooming the camera will center on the mouse cursor
@@ -393,6 +1536,83 @@ pub struct PanCam {
     pub mouse_wheel_sensitivity: f32,
     /// Adjust the zoom sensitivity of [`PinchGesture`] events.
     pub pinch_gesture_sensitivity: f32,
+    /// When set, the camera keeps gliding and gradually slows down after a
+    /// grab button is released
+    ///
+    /// The glide is clamped to the camera bounds like any other movement.
+    pub momentum: Option<Momentum>,
+    /// When set, zoom input sets a target scale that the projection eases
+    /// towards over several frames instead of jumping to it immediately
+    ///
+    /// The value is the exponential decay rate per second of the remaining
+    /// distance to the target scale; higher values settle faster. Around `10.`
+    /// gives smooth zooming that still feels responsive.
+    pub zoom_smoothing: Option<f32>,
+    /// When set, dragging past the bounds is possible with increasing
+    /// resistance, and the camera springs back inside the bounds on release
+    ///
+    /// When `None`, the camera stops hard at the bounds.
+    pub elastic_bounds: Option<ElasticBounds>,
+    /// When set, the camera moves when the cursor is close to the edge of the
+    /// camera's viewport
+    pub edge_scroll: Option<EdgeScroll>,
+    /// Whether touch input controls the camera
+    ///
+    /// Dragging with one finger pans the camera, and pinching with two
+    /// fingers zooms around the point between them.
+    pub touch_controls: bool,
+    /// When set, the camera can be panned and zoomed with a gamepad
+    ///
+    /// Panning uses [`PanCam::speed`] like keyboard movement.
+    pub gamepad: Option<GamepadControls>,
+    /// When set, the camera can be rotated around the middle of the view
+    pub rotation: Option<RotationControls>,
+    /// What scrolling with a mouse wheel or trackpad does
+    pub scroll_behavior: ScrollBehavior,
+    /// How scrolling is turned into zooming, see also
+    /// [`PanCam::mouse_wheel_sensitivity`]
+    pub wheel_zoom: WheelZoom,
+    /// When set, zooming steps through these projection scales instead of
+    /// changing the scale continuously
+    ///
+    /// Each wheel notch or zoom key press moves to the next level, and
+    /// pinching settles on the nearest level once it ends. Levels outside of
+    /// [`PanCam::min_scale`] and [`PanCam::max_scale`], or that would show
+    /// more than the bounds, are never reached.
+    pub zoom_steps: Option<Vec<f32>>,
+    /// When true, the camera position is rounded to whole physical pixels
+    /// whenever panning, zooming or clamping moves it
+    ///
+    /// This keeps pixel art from shimmering while panning at integer scales.
+    /// The unrounded position is kept internally, so slow pans still move.
+    pub pixel_snap: bool,
+    /// What the camera does horizontally when the bounds are narrower than
+    /// the view
+    pub small_bounds_x: SmallBounds,
+    /// What the camera does vertically when the bounds are shorter than the
+    /// view
+    pub small_bounds_y: SmallBounds,
+    /// Bounds of any shape, such as a [`ConvexPolygonBounds`], a
+    /// [`CircleBounds`] or a custom [`BoundsShape`]
+    ///
+    /// They apply on top of the rectangular bounds, which are usually left
+    /// infinite when using this.
+    #[reflect(ignore)]
+    pub shape_bounds: Option<ShapeBounds>,
+    /// How far the view may scroll past the bounds, including
+    /// [`PanCam::shape_bounds`]
+    pub bounds_padding: BoundsPadding,
+    /// When true, the world repeats horizontally every [`PanCam::max_x`] -
+    /// [`PanCam::min_x`] world units
+    ///
+    /// Instead of stopping at the bounds, the camera is moved back by the
+    /// width of the world when its position crosses them, triggering
+    /// [`PanCamWrapped`]. Zooming out is limited so the world is never shown
+    /// twice side by side. Needs finite bounds.
+    pub wrap_x: bool,
+    /// When true, the world repeats vertically every [`PanCam::max_y`] -
+    /// [`PanCam::min_y`] world units, like [`PanCam::wrap_x`]
+    pub wrap_y: bool,
 }
 
 impl PanCam {
@@ -415,9 +1635,34 @@ impl PanCam {
         Aabb2d { min, max }
     }
 
-    /// Returns the scale inclusive range
-    fn scale_range(&self) -> RangeInclusive<f32> {
-        self.min_scale..=self.max_scale
+    /// Returns the size of the bounds that limits zooming out, which is
+    /// infinite on axes where the view may get bigger than the bounds
+    ///
+    /// Wrapping axes are limited by [`PanCam::wrap_size`] instead.
+    fn zoom_bounds_size(&self) -> Vec2 {
+        let size = self.rect().size();
+        let limit = |size: f32, small_bounds, wrap: bool| match small_bounds {
+            SmallBounds::ZoomIn if !wrap => size,
+            _ => f32::INFINITY,
+        };
+        let size = vec2(
+            limit(size.x, self.small_bounds_x, self.wrap_x),
+            limit(size.y, self.small_bounds_y, self.wrap_y),
+        );
+        match &self.shape_bounds {
+            Some(shape_bounds) => size.min(shape_bounds.zoom_bounds_size()),
+            None => size,
+        }
+    }
+
+    /// Returns the size of the world that repeats on wrapping axes, which is
+    /// infinite on other axes
+    fn wrap_size(&self) -> Vec2 {
+        let size = self.rect().size();
+        vec2(
+            if self.wrap_x { size.x } else { f32::INFINITY },
+            if self.wrap_y { size.y } else { f32::INFINITY },
+        )
     }
 }
 
@@ -426,6 +1671,8 @@ impl Default for PanCam {
         Self {
             move_keys: DirectionKeys::arrows_and_wasd(),
             speed: 200.,
+            zoom_keys: ZoomKeys::NONE,
+            zoom_speed: 2.,
             grab_buttons: vec![MouseButton::Left, MouseButton::Right, MouseButton::Middle],
             enabled: true,
             zoom_to_cursor: true,
@@ -437,31 +1684,54 @@ impl Default for PanCam {
             max_y: f32::INFINITY,
             mouse_wheel_sensitivity: 1.,
             pinch_gesture_sensitivity: 1.,
+            momentum: None,
+            zoom_smoothing: None,
+            elastic_bounds: None,
+            edge_scroll: None,
+            touch_controls: true,
+            gamepad: None,
+            rotation: None,
+            scroll_behavior: ScrollBehavior::Zoom,
+            wheel_zoom: WheelZoom::default(),
+            zoom_steps: None,
+            pixel_snap: false,
+            small_bounds_x: SmallBounds::ZoomIn,
+            small_bounds_y: SmallBounds::ZoomIn,
+            shape_bounds: None,
+            bounds_padding: BoundsPadding::NONE,
+            wrap_x: false,
+            wrap_y: false,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::f32::INFINITY;
+    use std::f32::{
+        INFINITY,
+        consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4},
+    };
 
-    use bevy::prelude::OrthographicProjection;
+    use bevy::{input::ButtonState, window::WindowRef};
 
     use super::*;
-
-    /// Simple mock function to construct a square projection from a window size
-    fn mock_proj(window_size: Vec2) -> OrthographicProjection {
-        let mut proj = OrthographicProjection::default_2d();
-        proj.update(window_size.x, window_size.y);
-        proj
-    }
+    use crate::test_utils::{
+        input_app, mock_proj, mouse_button, move_cursor, scale, scroll, viewport_camera,
+    };
 
     #[test]
     fn bounds_matching_window_width_have_max_scale_1() {
         let window_size = vec2(100., 100.);
         let proj = mock_proj(window_size);
         assert_eq!(
-            max_scale_within_bounds(vec2(100., INFINITY), &proj, window_size).x,
+            max_scale_within_bounds(
+                vec2(100., INFINITY),
+                BoundsPadding::NONE,
+                &proj,
+                window_size,
+                Quat::IDENTITY
+            )
+            .x,
             1.
         );
     }
@@ -472,7 +1742,14 @@ mod tests {
         let window_size = vec2(100., 100.);
         let proj = mock_proj(window_size);
         assert_eq!(
-            max_scale_within_bounds(vec2(50., INFINITY), &proj, window_size).x,
+            max_scale_within_bounds(
+                vec2(50., INFINITY),
+                BoundsPadding::NONE,
+                &proj,
+                window_size,
+                Quat::IDENTITY
+            )
+            .x,
             0.5
         );
     }
@@ -483,7 +1760,14 @@ mod tests {
         let window_size = vec2(100., 100.);
         let proj = mock_proj(window_size);
         assert_eq!(
-            max_scale_within_bounds(vec2(200., INFINITY), &proj, window_size).x,
+            max_scale_within_bounds(
+                vec2(200., INFINITY),
+                BoundsPadding::NONE,
+                &proj,
+                window_size,
+                Quat::IDENTITY
+            )
+            .x,
             2.
         );
     }
@@ -493,7 +1777,14 @@ mod tests {
         let window_size = vec2(100., 100.);
         let proj = mock_proj(window_size);
         assert_eq!(
-            max_scale_within_bounds(vec2(INFINITY, 100.), &proj, window_size).y,
+            max_scale_within_bounds(
+                vec2(INFINITY, 100.),
+                BoundsPadding::NONE,
+                &proj,
+                window_size,
+                Quat::IDENTITY
+            )
+            .y,
             1.
         );
     }
@@ -504,7 +1795,14 @@ mod tests {
         let window_size = vec2(100., 100.);
         let proj = mock_proj(window_size);
         assert_eq!(
-            max_scale_within_bounds(vec2(INFINITY, 50.), &proj, window_size).y,
+            max_scale_within_bounds(
+                vec2(INFINITY, 50.),
+                BoundsPadding::NONE,
+                &proj,
+                window_size,
+                Quat::IDENTITY
+            )
+            .y,
             0.5
         );
     }
@@ -515,8 +1813,539 @@ mod tests {
         let window_size = vec2(100., 100.);
         let proj = mock_proj(window_size);
         assert_eq!(
-            max_scale_within_bounds(vec2(INFINITY, 200.), &proj, window_size).y,
+            max_scale_within_bounds(
+                vec2(INFINITY, 200.),
+                BoundsPadding::NONE,
+                &proj,
+                window_size,
+                Quat::IDENTITY
+            )
+            .y,
             2.
         );
     }
+
+    #[test]
+    fn bounds_padding_allows_zooming_out_further() {
+        let window_size = vec2(100., 100.);
+        let proj = mock_proj(window_size);
+        let max_scale = |padding| {
+            max_scale_within_bounds(
+                vec2(100., INFINITY),
+                padding,
+                &proj,
+                window_size,
+                Quat::IDENTITY,
+            )
+            .x
+        };
+        assert_eq!(max_scale(BoundsPadding::World(25.)), 1.5);
+        assert_eq!(max_scale(BoundsPadding::ViewportFraction(0.25)), 2.);
+        assert_eq!(max_scale(BoundsPadding::ViewportFraction(0.5)), INFINITY);
+    }
+
+    #[test]
+    fn bounds_padding_allows_scrolling_past_the_bounds() {
+        let mut pan_cam = PanCam {
+            min_x: -100.,
+            max_x: 100.,
+            min_y: -100.,
+            max_y: 100.,
+            bounds_padding: BoundsPadding::World(20.),
+            ..default()
+        };
+        let view_size = vec2(100., 100.);
+        assert_eq!(
+            clamp_to_safe_zone(vec2(500., -500.), &pan_cam, view_size),
+            vec2(70., -70.)
+        );
+
+        pan_cam.bounds_padding = BoundsPadding::ViewportFraction(0.2);
+        assert_eq!(
+            clamp_to_safe_zone(vec2(500., -500.), &pan_cam, view_size),
+            vec2(70., -70.)
+        );
+    }
+
+    /// Bounds 100 wide and 50 tall around the origin, with a 200 by 200 view
+    fn small_bounds_pos(pos: Vec2, small_bounds: SmallBounds) -> Vec2 {
+        let pan_cam = PanCam {
+            min_x: -50.,
+            max_x: 50.,
+            min_y: -25.,
+            max_y: 25.,
+            small_bounds_x: small_bounds,
+            small_bounds_y: small_bounds,
+            ..default()
+        };
+        clamp_to_safe_zone(pos, &pan_cam, vec2(200., 200.))
+    }
+
+    #[test]
+    fn small_bounds_are_centered() {
+        let pos = vec2(30., -80.);
+        assert_eq!(small_bounds_pos(pos, SmallBounds::ZoomIn), Vec2::ZERO);
+        assert_eq!(small_bounds_pos(pos, SmallBounds::Center), Vec2::ZERO);
+    }
+
+    #[test]
+    fn small_bounds_align_to_the_view_edges() {
+        let pos = vec2(30., -80.);
+        // The left and bottom edges of the view are on the bounds
+        assert_eq!(small_bounds_pos(pos, SmallBounds::AlignMin), vec2(50., 75.));
+        // The right and top edges of the view are on the bounds
+        assert_eq!(
+            small_bounds_pos(pos, SmallBounds::AlignMax),
+            vec2(-50., -75.)
+        );
+    }
+
+    #[test]
+    fn small_bounds_move_freely_inside_the_view() {
+        assert_eq!(
+            small_bounds_pos(vec2(30., -60.), SmallBounds::Free),
+            vec2(30., -60.)
+        );
+        assert_eq!(
+            small_bounds_pos(vec2(60., -80.), SmallBounds::Free),
+            vec2(50., -75.)
+        );
+    }
+
+    #[test]
+    fn small_bounds_only_limit_zoom_when_zooming_in() {
+        let pan_cam = PanCam {
+            min_x: -50.,
+            max_x: 50.,
+            min_y: -25.,
+            max_y: 25.,
+            small_bounds_y: SmallBounds::Center,
+            ..default()
+        };
+        assert_eq!(pan_cam.zoom_bounds_size(), vec2(100., INFINITY));
+    }
+
+    #[test]
+    fn release_velocity_averages_recent_samples() {
+        let mut drag_state = DragState::default();
+        drag_state.push_sample(0.0, 0.1, vec2(100., 0.), 0.25);
+        drag_state.push_sample(0.1, 0.1, vec2(10., 0.), 0.25);
+        drag_state.push_sample(0.2, 0.1, vec2(10., 20.), 0.25);
+        drag_state.push_sample(0.3, 0.1, vec2(10., 0.), 0.25);
+        // the first sample is older than the sample window
+        let velocity = drag_state.release_velocity();
+        assert!(velocity.abs_diff_eq(vec2(100., 200. / 3.), 0.001));
+        assert_eq!(drag_state.release_velocity(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn fitting_uses_the_tightest_axis() {
+        let window_size = vec2(100., 100.);
+        let proj = mock_proj(window_size);
+        assert_eq!(scale_to_fit(vec2(200., 50.), &proj, window_size, 0.), 2.);
+        assert_eq!(scale_to_fit(vec2(20., 50.), &proj, window_size, 0.), 0.5);
+    }
+
+    #[test]
+    fn fitting_leaves_room_for_padding() {
+        let window_size = vec2(100., 100.);
+        let proj = mock_proj(window_size);
+        assert_eq!(scale_to_fit(vec2(100., 100.), &proj, window_size, 25.), 2.);
+    }
+
+    #[test]
+    fn rotated_view_extent_covers_the_whole_view() {
+        let size = vec2(100., 50.);
+        assert_eq!(view_extent(size, Quat::IDENTITY), size);
+        assert!(
+            view_extent(size, Quat::from_rotation_z(FRAC_PI_2)).abs_diff_eq(vec2(50., 100.), 0.001)
+        );
+        assert!(
+            view_extent(size, Quat::from_rotation_z(FRAC_PI_4))
+                .abs_diff_eq(Vec2::splat(150. * FRAC_1_SQRT_2), 0.001)
+        );
+    }
+
+    #[test]
+    fn rotated_view_zooms_in_to_stay_within_bounds() {
+        let window_size = vec2(100., 100.);
+        let mut proj = mock_proj(window_size);
+        let pan_cam = PanCam {
+            min_x: -50.,
+            max_x: 50.,
+            min_y: -50.,
+            max_y: 50.,
+            min_scale: 0.1,
+            max_scale: 10.,
+            ..default()
+        };
+        constrain_proj_scale(
+            &mut proj,
+            &pan_cam,
+            window_size,
+            Quat::from_rotation_z(FRAC_PI_4),
+        );
+        assert!((proj.scale - FRAC_1_SQRT_2).abs() < 0.001);
+    }
+
+    #[test]
+    fn clamping_reports_hitting_the_bounds() {
+        #[derive(Resource, Default)]
+        struct Hits(Vec<PanCamHitBounds>);
+
+        let mut app = App::new();
+        app.init_resource::<Hits>()
+            .add_observer(on_clamp_bounds)
+            .add_observer(|hit: On<PanCamHitBounds>, mut hits: ResMut<Hits>| {
+                hits.0.push(*hit);
+            });
+
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    min_x: -100.,
+                    max_x: 100.,
+                    min_y: -100.,
+                    max_y: 100.,
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(500., 0., 0.),
+            ))
+            .id();
+        app.world_mut()
+            .trigger(PanCamClampBounds { entity: camera });
+        app.world_mut().flush();
+
+        let hits = &app.world().resource::<Hits>().0;
+        assert_eq!(
+            hits.as_slice(),
+            [PanCamHitBounds {
+                entity: camera,
+                proposed_translation: vec2(500., 0.),
+                translation: vec2(50., 0.),
+            }]
+        );
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(50., 0., 0.));
+    }
+
+    #[test]
+    fn keyboard_zoom_keeps_the_view_within_bounds() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_zoom);
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    zoom_keys: ZoomKeys::plus_minus(),
+                    min_x: -100.,
+                    max_x: 100.,
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(50., 0., 0.),
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Minus);
+        app.update();
+        app.update();
+
+        // Zooming out around the middle moves the camera away from the edge
+        // in the same frame
+        let scale = scale(&app, camera);
+        assert!(scale > 1.);
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation.x, 100. - 50. * scale);
+    }
+
+    #[test]
+    fn input_goes_to_the_topmost_viewport() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_zoom);
+        let spawn_camera = |app: &mut App, camera: Camera, width: f32| {
+            app.world_mut()
+                .spawn((
+                    PanCam::default(),
+                    camera,
+                    Projection::Orthographic(mock_proj(vec2(width, 100.))),
+                ))
+                .id()
+        };
+        let full = spawn_camera(&mut app, viewport_camera(0, 100, 0), 100.);
+        let inset = spawn_camera(&mut app, viewport_camera(50, 50, 1), 50.);
+
+        // Where the viewports overlap, the camera rendered last gets the input
+        move_cursor(&mut app, Some(vec2(75., 50.)));
+        scroll(&mut app);
+        app.update();
+        assert_eq!(scale(&app, full), 1.);
+        assert!(scale(&app, inset) < 1.);
+
+        let inset_scale = scale(&app, inset);
+        move_cursor(&mut app, Some(vec2(25., 50.)));
+        scroll(&mut app);
+        app.update();
+        assert!(scale(&app, full) < 1.);
+        assert_eq!(scale(&app, inset), inset_scale);
+    }
+
+    #[test]
+    fn drags_stay_with_the_camera_they_started_on() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_movement);
+        let spawn_camera = |app: &mut App, camera: Camera| {
+            app.world_mut()
+                .spawn((
+                    PanCam::default(),
+                    camera,
+                    Projection::Orthographic(mock_proj(vec2(50., 100.))),
+                ))
+                .id()
+        };
+        let left = spawn_camera(&mut app, viewport_camera(0, 50, 0));
+        let right = spawn_camera(&mut app, viewport_camera(50, 50, 0));
+
+        move_cursor(&mut app, Some(vec2(40., 50.)));
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Pressed);
+        app.update();
+
+        // Crossing into the other viewport keeps dragging the first camera
+        move_cursor(&mut app, Some(vec2(60., 40.)));
+        app.update();
+        let transform = app.world().get::<Transform>(left).unwrap();
+        assert_eq!(transform.translation, vec3(-20., -10., 0.));
+        let transform = app.world().get::<Transform>(right).unwrap();
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn input_goes_to_the_camera_of_the_window_with_the_cursor() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_zoom);
+        let other_window = app
+            .world_mut()
+            .spawn(Window {
+                resolution: (100, 100).into(),
+                ..default()
+            })
+            .id();
+        let primary = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+        let other = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                RenderTarget::Window(WindowRef::Entity(other_window)),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+
+        app.world_mut()
+            .get_mut::<Window>(other_window)
+            .unwrap()
+            .set_cursor_position(Some(vec2(50., 50.)));
+        scroll(&mut app);
+        app.update();
+        assert_eq!(scale(&app, primary), 1.);
+        assert!(scale(&app, other) < 1.);
+    }
+
+    #[test]
+    fn cursor_delta_ignores_leaving_and_reentering_the_window() {
+        let window = Entity::from_raw_u32(1).unwrap();
+        let other_window = Entity::from_raw_u32(2).unwrap();
+        let mut drag_state = DragState::default();
+
+        assert_eq!(
+            drag_state.cursor_delta(window, Some(vec2(10., 10.))),
+            Vec2::ZERO
+        );
+        assert_eq!(
+            drag_state.cursor_delta(window, Some(vec2(15., 20.))),
+            vec2(5., -10.)
+        );
+
+        // The cursor leaves and comes back somewhere else
+        assert_eq!(drag_state.cursor_delta(window, None), Vec2::ZERO);
+        assert_eq!(
+            drag_state.cursor_delta(window, Some(vec2(90., 90.))),
+            Vec2::ZERO
+        );
+        assert_eq!(
+            drag_state.cursor_delta(window, Some(vec2(91., 90.))),
+            vec2(1., 0.)
+        );
+
+        // Positions in different windows can't be compared
+        assert_eq!(
+            drag_state.cursor_delta(other_window, Some(vec2(0., 0.))),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn changing_the_bounds_clamps_the_camera() {
+        let mut app = App::new();
+        app.add_systems(Update, clamp_changed_bounds)
+            .add_observer(on_clamp_bounds);
+        app.world_mut().spawn((
+            Window {
+                resolution: (100, 100).into(),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(500., 0., 0.),
+            ))
+            .id();
+
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(500., 0., 0.));
+
+        let mut pan_cam = app.world_mut().get_mut::<PanCam>(camera).unwrap();
+        pan_cam.min_x = -100.;
+        pan_cam.max_x = 100.;
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(50., 0., 0.));
+
+        // Bounds narrower than the view zoom the camera in
+        let mut pan_cam = app.world_mut().get_mut::<PanCam>(camera).unwrap();
+        pan_cam.min_x = -25.;
+        pan_cam.max_x = 25.;
+        app.update();
+        let Projection::Orthographic(proj) = app.world().get::<Projection>(camera).unwrap() else {
+            unreachable!();
+        };
+        assert_eq!(proj.scale, 0.5);
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(0., 0., 0.));
+    }
+
+    #[test]
+    fn smooth_zoom_eases_towards_the_target_around_the_cursor() {
+        let mut app = input_app();
+        // Refresh the projection area like the camera systems would
+        app.add_systems(Update, do_camera_zoom).add_systems(
+            PostUpdate,
+            |mut projections: Query<&mut Projection>| {
+                for mut projection in &mut projections {
+                    projection.update(100., 100.);
+                }
+            },
+        );
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    zoom_smoothing: Some(10.),
+                    min_scale: 0.5,
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+        let x = |app: &App| app.world().get::<Transform>(camera).unwrap().translation.x;
+        app.update();
+
+        // Zooming in far past the minimum only eases towards it. The world
+        // point 25 units right of the middle stays under the cursor.
+        move_cursor(&mut app, Some(vec2(75., 50.)));
+        for _ in 0..20 {
+            scroll(&mut app);
+        }
+        app.update();
+        let mut prev_scale = scale(&app, camera);
+        assert!(prev_scale < 1. && prev_scale > 0.5);
+        assert!((x(&app) + 25. * prev_scale - 25.).abs() < 0.001);
+
+        // Moving the cursor away doesn't change the point zoomed around
+        move_cursor(&mut app, Some(vec2(25., 50.)));
+        for _ in 0..5 {
+            app.update();
+            let scale = scale(&app, camera);
+            assert!(scale < prev_scale && scale >= 0.5);
+            assert!((x(&app) + 25. * scale - 25.).abs() < 0.001);
+            prev_scale = scale;
+        }
+
+        for _ in 0..50 {
+            app.update();
+        }
+        assert_eq!(scale(&app, camera), 0.5);
+        assert!((x(&app) - 12.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn setting_the_scale_clamps_the_camera() {
+        let mut app = App::new();
+        app.add_systems(Update, clamp_changed_bounds)
+            .add_observer(on_clamp_bounds);
+        app.world_mut().spawn((
+            Window {
+                resolution: (100, 100).into(),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    min_x: -100.,
+                    max_x: 100.,
+                    max_scale: 1.5,
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_xyz(50., 0., 0.),
+            ))
+            .id();
+        app.update();
+
+        let set_scale = |app: &mut App, scale: f32| {
+            let mut projection = app.world_mut().get_mut::<Projection>(camera).unwrap();
+            let Projection::Orthographic(proj) = &mut *projection else {
+                unreachable!();
+            };
+            proj.scale = scale;
+        };
+        let scale_and_x = |app: &App| {
+            let Projection::Orthographic(proj) = app.world().get::<Projection>(camera).unwrap()
+            else {
+                unreachable!();
+            };
+            let transform = app.world().get::<Transform>(camera).unwrap();
+            (proj.scale, transform.translation.x)
+        };
+
+        // A wider view pushes the camera away from the edge
+        set_scale(&mut app, 1.5);
+        app.update();
+        assert_eq!(scale_and_x(&app), (1.5, 25.));
+
+        // Scales past the limits are constrained
+        set_scale(&mut app, 10.);
+        app.update();
+        assert_eq!(scale_and_x(&app), (1.5, 25.));
+        set_scale(&mut app, 0.);
+        app.update();
+        assert_eq!(scale_and_x(&app).0, PanCam::default().min_scale);
+    }
 }
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+
+use crate::DragState;
+
+/// Settings for kinetic panning, where the camera keeps gliding after a grab
+/// button is released
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct Momentum {
+    /// How quickly the camera slows down, as an exponential decay rate per
+    /// second. Higher values stop the camera sooner.
+    pub friction: f32,
+    /// How many seconds of the most recent drag movement are used to estimate
+    /// the release velocity
+    pub sample_window: f32,
+    /// The glide stops once the camera is slower than this, in logical pixels
+    /// per second
+    pub min_speed: f32,
+}
+
+impl Default for Momentum {
+    fn default() -> Self {
+        Self {
+            friction: 5.,
+            sample_window: 0.1,
+            min_speed: 5.,
+        }
+    }
+}
+
+impl Momentum {
+    /// Records the drag of a grabbed camera, starts gliding once it's
+    /// released and slows the glide down
+    ///
+    /// `drag_delta` is how far the drag moved the camera this frame, if it's
+    /// being dragged. Returns how far the camera glides this frame, in world
+    /// units.
+    pub(crate) fn glide(
+        &self,
+        drag_state: &mut DragState,
+        was_grabbed: bool,
+        drag_delta: Option<Vec2>,
+        time: &Time<Real>,
+        world_units_per_pixel: Vec2,
+    ) -> Vec2 {
+        if drag_state.grabbed {
+            drag_state.velocity = Vec2::ZERO;
+            if let Some(drag_delta) = drag_delta {
+                drag_state.push_sample(
+                    time.elapsed_secs(),
+                    time.delta_secs(),
+                    -drag_delta,
+                    self.sample_window,
+                );
+            }
+            return Vec2::ZERO;
+        }
+
+        if was_grabbed {
+            drag_state.velocity = drag_state.release_velocity();
+            return Vec2::ZERO;
+        }
+
+        let glide_delta = -drag_state.velocity * time.delta_secs();
+        drag_state.velocity *= (-self.friction * time.delta_secs()).exp();
+        if (drag_state.velocity / world_units_per_pixel).length() < self.min_speed {
+            drag_state.velocity = Vec2::ZERO;
+        }
+        glide_delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::ButtonState;
+
+    use super::*;
+    use crate::{
+        PanCam, do_camera_movement,
+        test_utils::{input_app, mock_proj, mouse_button, move_cursor},
+    };
+
+    /// Drags a gliding camera 20 pixels left and down over two frames, then
+    /// releases it
+    fn drag_and_release(pan_cam: PanCam) -> (App, Entity) {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_movement);
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    momentum: Some(Momentum {
+                        friction: 5.,
+                        sample_window: 0.5,
+                        min_speed: 20.,
+                    }),
+                    ..pan_cam
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+        app.update();
+
+        move_cursor(&mut app, Some(vec2(50., 50.)));
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Pressed);
+        app.update();
+        for cursor_pos in [vec2(40., 60.), vec2(30., 70.)] {
+            move_cursor(&mut app, Some(cursor_pos));
+            app.update();
+        }
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Released);
+        app.update();
+        (app, camera)
+    }
+
+    fn position(app: &App, camera: Entity) -> Vec2 {
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        transform.translation.truncate()
+    }
+
+    #[test]
+    fn released_cameras_glide_until_friction_stops_them() {
+        let (mut app, camera) = drag_and_release(PanCam::default());
+        assert_eq!(position(&app, camera), vec2(20., 20.));
+
+        // Each frame glides less far than the one before
+        let mut steps = vec![];
+        for _ in 0..8 {
+            let previous = position(&app, camera);
+            app.update();
+            steps.push(position(&app, camera).x - previous.x);
+        }
+        let gliding = steps.iter().take_while(|step| **step > 0.).count();
+        assert!(gliding >= 3);
+        assert!(steps[..gliding].windows(2).all(|steps| steps[1] < steps[0]));
+
+        // Until it's slower than the minimum speed
+        assert!(steps[gliding..].iter().all(|step| *step == 0.));
+        let drag_state = app.world().get::<DragState>(camera).unwrap();
+        assert_eq!(drag_state.velocity, Vec2::ZERO);
+    }
+
+    #[test]
+    fn gliding_stops_along_the_axis_that_hits_the_bounds() {
+        let (mut app, camera) = drag_and_release(PanCam {
+            min_x: -75.,
+            max_x: 75.,
+            ..default()
+        });
+
+        app.update();
+        app.update();
+        let position = position(&app, camera);
+        assert_eq!(position.x, 25.);
+        assert!(position.y > 25.);
+        let drag_state = app.world().get::<DragState>(camera).unwrap();
+        assert_eq!(drag_state.velocity.x, 0.);
+        assert!(drag_state.velocity.y > 0.);
+    }
+}
@@ -4,48 +4,158 @@ use bevy::input::{
 };
 use bevy::prelude::*;
 
-/// Holds normalized zoom inputs constructed from
-/// raw events.
+use crate::{ScrollBehavior, WheelZoom};
+
+/// The keys that make pixel scrolling zoom with
+/// [`ScrollBehavior::PixelScrollPans`]
+const ZOOM_MODIFIERS: [KeyCode; 4] = [
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+/// Holds zoom inputs constructed from raw events, to be normalized with the
+/// [`WheelZoom`] settings of each camera.
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct NormalizedZoomInputs {
     pub pinch: f32,
-    pub wheel: f32,
+    /// Scrolling from line based mouse wheels, in lines
+    pub line_wheel: f32,
+    /// Scrolling from pixel based devices like trackpads, in pixels
+    pub pixel_wheel: f32,
 }
 
 impl NormalizedZoomInputs {
-    /// Reads [`MouseWheel`] and [`PinchGesture`] [`MessageReader`]s, normalizes
-    /// them, and returns a new [`NormalizedZoomInputs`].
+    /// Reads [`MouseWheel`] and [`PinchGesture`] [`MessageReader`]s and
+    /// returns a new [`NormalizedZoomInputs`].
     pub(crate) fn from_events(
         mut mouse_wheel_events: MessageReader<MouseWheel>,
         mut pinch_gesture_events: MessageReader<PinchGesture>,
     ) -> Self {
         let pinch = pinch_gesture_events.read().map(|ev| ev.0).sum::<f32>();
 
-        // Wheel event deltas are roughly 1000 larger scale than equivalent
-        // pinchs and way too large to apply as-is, so we normalize them by
-        // dividing by 1000.
-        const MOUSE_WHEEL_NORMALIZE_FACTOR: f32 = 0.001;
-        const PIXELS_PER_LINE: f32 = 100.; // Maybe make configurable?
-        let wheel = mouse_wheel_events
-            .read()
-            .map(|ev| match ev.unit {
-                MouseScrollUnit::Pixel => ev.y,
-                MouseScrollUnit::Line => ev.y * PIXELS_PER_LINE,
-            })
-            .sum::<f32>()
-            * MOUSE_WHEEL_NORMALIZE_FACTOR;
-
-        Self { pinch, wheel }
+        let mut line_wheel = 0.;
+        let mut pixel_wheel = 0.;
+        for ev in mouse_wheel_events.read() {
+            match ev.unit {
+                MouseScrollUnit::Pixel => pixel_wheel += ev.y,
+                MouseScrollUnit::Line => line_wheel += ev.y,
+            }
+        }
+
+        Self {
+            pinch,
+            line_wheel,
+            pixel_wheel,
+        }
+    }
+
+    /// Drops the inputs that don't zoom with the given scroll behavior.
+    pub(crate) fn with_scroll_behavior(
+        mut self,
+        scroll_behavior: ScrollBehavior,
+        keyboard_buttons: &ButtonInput<KeyCode>,
+    ) -> Self {
+        if pixel_scroll_pans(scroll_behavior, keyboard_buttons) {
+            self.pixel_wheel = 0.;
+        }
+        self
+    }
+
+    /// Normalize the wheel inputs, apply sensitivity scalers to the inputs and
+    /// return a final zoom delta to apply.
+    pub(crate) fn apply_sensitivity(
+        &self,
+        wheel_zoom: &WheelZoom,
+        wheel_sensitivity: f32,
+        pinch_sensitivity: f32,
+    ) -> f32 {
+        let pixels = self.line_wheel * wheel_zoom.pixels_per_line * wheel_zoom.line_sensitivity
+            + self.pixel_wheel * wheel_zoom.pixel_sensitivity;
+        let direction = if wheel_zoom.invert { -1. } else { 1. };
+        let wheel = direction * pixels * wheel_zoom.normalize_factor;
+
+        self.pinch * pinch_sensitivity + wheel * wheel_sensitivity
     }
 
-    /// Apply sensitivity scalers to the inputs and return a final zoom delta
-    /// to apply.
-    pub(crate) fn apply_sensitivity(&self, wheel_sensitivity: f32, pinch_sensitivity: f32) -> f32 {
-        self.pinch * pinch_sensitivity + self.wheel * wheel_sensitivity
+    /// Returns the wheel scrolling in zoom steps, where one line of scrolling
+    /// is one step. Positive steps zoom in.
+    pub(crate) fn wheel_steps(&self, wheel_zoom: &WheelZoom) -> f32 {
+        let lines = self.line_wheel * wheel_zoom.line_sensitivity
+            + self.pixel_wheel * wheel_zoom.pixel_sensitivity / wheel_zoom.pixels_per_line;
+        if wheel_zoom.invert { -lines } else { lines }
     }
 
     /// True when no input.
     pub(crate) fn is_empty(self) -> bool {
-        self.pinch == 0. && self.wheel == 0.
+        self.pinch == 0. && self.line_wheel == 0. && self.pixel_wheel == 0.
+    }
+}
+
+/// Whether pixel scrolling pans rather than zooms, given the scroll behavior
+/// and the held zoom modifiers.
+pub(crate) fn pixel_scroll_pans(
+    scroll_behavior: ScrollBehavior,
+    keyboard_buttons: &ButtonInput<KeyCode>,
+) -> bool {
+    scroll_behavior == ScrollBehavior::PixelScrollPans
+        && !keyboard_buttons.any_pressed(ZOOM_MODIFIERS)
+}
+
+/// Sums the [`MouseScrollUnit::Pixel`] scrolling, in logical pixels the
+/// content should move right and down.
+pub(crate) fn pixel_scroll(mut mouse_wheel_events: MessageReader<MouseWheel>) -> Vec2 {
+    mouse_wheel_events
+        .read()
+        .filter(|ev| ev.unit == MouseScrollUnit::Pixel)
+        .map(|ev| vec2(ev.x, ev.y))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixel_scroll_only_zooms_with_modifier_when_it_pans() {
+        let inputs = NormalizedZoomInputs {
+            pinch: 0.,
+            line_wheel: 1.,
+            pixel_wheel: 200.,
+        };
+        let wheel_zoom = WheelZoom::default();
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+
+        let zoom = inputs.with_scroll_behavior(ScrollBehavior::Zoom, &keyboard);
+        assert!((zoom.apply_sensitivity(&wheel_zoom, 1., 1.) - 0.3).abs() < 0.001);
+
+        let pan = inputs.with_scroll_behavior(ScrollBehavior::PixelScrollPans, &keyboard);
+        assert!((pan.apply_sensitivity(&wheel_zoom, 1., 1.) - 0.1).abs() < 0.001);
+
+        keyboard.press(KeyCode::ControlLeft);
+        let modified = inputs.with_scroll_behavior(ScrollBehavior::PixelScrollPans, &keyboard);
+        assert!((modified.apply_sensitivity(&wheel_zoom, 1., 1.) - 0.3).abs() < 0.001);
+    }
+
+    #[test]
+    fn wheel_zoom_tunes_line_and_pixel_scrolling_separately() {
+        let inputs = NormalizedZoomInputs {
+            pinch: 0.,
+            line_wheel: 1.,
+            pixel_wheel: 200.,
+        };
+        let wheel_zoom = WheelZoom {
+            pixels_per_line: 50.,
+            pixel_sensitivity: 0.25,
+            ..default()
+        };
+        assert!((inputs.apply_sensitivity(&wheel_zoom, 1., 1.) - 0.1).abs() < 0.001);
+
+        let inverted = WheelZoom {
+            invert: true,
+            ..wheel_zoom
+        };
+        assert!((inputs.apply_sensitivity(&inverted, 1., 1.) + 0.1).abs() < 0.001);
     }
 }
@@ -0,0 +1,333 @@
+use bevy::{camera::RenderTarget, platform::collections::HashMap, prelude::*};
+
+use crate::{
+    DragState, PanCam, PanCamInputs, PanCamWindows, PanCamZoomed, SnapState, ZoomState,
+    clamp_to_safe_zone, constrain_proj_scale, hovered_cameras, trigger_moved, view_extent,
+};
+
+/// Controls for rotating the camera around the middle of its viewport
+///
+/// Panning, zooming and bounds keep working in the rotated view. The bounds
+/// stay axis aligned in the world, so rotating may zoom in or move the camera
+/// to keep the whole view inside them.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct RotationControls {
+    /// The keys that rotate the camera counterclockwise, turning the view
+    /// clockwise
+    pub counterclockwise_keys: Vec<KeyCode>,
+    /// The keys that rotate the camera clockwise, turning the view
+    /// counterclockwise
+    pub clockwise_keys: Vec<KeyCode>,
+    /// Keyboard rotation speed in radians per second
+    pub speed: f32,
+    /// Holding any of these keys while dragging with a grab button rotates
+    /// the view around the middle of the viewport instead of panning it
+    pub drag_modifiers: Vec<KeyCode>,
+    /// Adjust the sensitivity of [`RotationGesture`](bevy::input::gestures::RotationGesture) events, and of twisting
+    /// two fingers when [`PanCam::touch_controls`] is enabled
+    pub gesture_sensitivity: f32,
+}
+
+impl Default for RotationControls {
+    fn default() -> Self {
+        Self {
+            counterclockwise_keys: vec![KeyCode::KeyQ],
+            clockwise_keys: vec![KeyCode::KeyE],
+            speed: 2.,
+            drag_modifiers: vec![KeyCode::AltLeft, KeyCode::AltRight],
+            gesture_sensitivity: 1.,
+        }
+    }
+}
+
+impl RotationControls {
+    /// Returns `1.` when rotating counterclockwise, `-1.` when rotating
+    /// clockwise and `0.` otherwise
+    fn direction(&self, keyboard_buttons: &ButtonInput<KeyCode>) -> f32 {
+        let mut direction = 0.;
+
+        if keyboard_buttons.any_pressed(self.counterclockwise_keys.iter().copied()) {
+            direction += 1.;
+        }
+
+        if keyboard_buttons.any_pressed(self.clockwise_keys.iter().copied()) {
+            direction -= 1.;
+        }
+
+        direction
+    }
+
+    /// Whether a modifier is held that turns dragging into rotating
+    pub(crate) fn drag_rotates(&self, keyboard_buttons: &ButtonInput<KeyCode>) -> bool {
+        keyboard_buttons.any_pressed(self.drag_modifiers.iter().copied())
+    }
+}
+
+pub(crate) fn do_camera_rotation(
+    mut query: Query<(
+        Entity,
+        &PanCam,
+        &Camera,
+        &RenderTarget,
+        &mut Projection,
+        &mut Transform,
+    )>,
+    mut states: Query<(&DragState, &mut ZoomState, &mut SnapState)>,
+    inputs: PanCamInputs,
+    windows: PanCamWindows,
+    mut commands: Commands,
+    mut last_drag_pos: Local<HashMap<Entity, Vec2>>,
+    time: Res<Time<Real>>,
+) {
+    let PanCamInputs {
+        keyboard_buttons,
+        touch_inputs,
+        mut rotation_events,
+        ..
+    } = inputs;
+
+    let gesture: f32 = rotation_events
+        .read()
+        .map(|gesture| gesture.0.to_radians())
+        .sum();
+
+    let primary_window = windows.primary();
+
+    // Like zooming, gestures go to the camera under the cursor or fingers
+    let touch_pinch = touch_inputs.routed_pinch(
+        primary_window,
+        query
+            .iter()
+            .map(|(entity, pan_cam, camera, target, ..)| (entity, pan_cam, camera, target)),
+    );
+
+    let cursor_positions = windows.cursor_positions();
+    let hovered_cameras = hovered_cameras(
+        &cursor_positions,
+        primary_window,
+        query
+            .iter()
+            .map(|(entity, pan_cam, camera, target, ..)| (entity, pan_cam, camera, target)),
+    );
+    let has_cursor = !cursor_positions.is_empty();
+
+    for (entity, pan_cam, camera, render_target, mut projection, mut transform) in &mut query {
+        let (true, Some(controls)) = (pan_cam.enabled, &pan_cam.rotation) else {
+            last_drag_pos.remove(&entity);
+            continue;
+        };
+
+        let Projection::Orthographic(proj) = &mut *projection else {
+            continue;
+        };
+
+        let Some((window_entity, window)) = windows.camera_window(render_target) else {
+            continue;
+        };
+
+        let Ok((drag_state, mut zoom_state, mut snap_state)) = states.get_mut(entity) else {
+            continue;
+        };
+
+        // Positive angles turn the camera counterclockwise. Gestures turn the
+        // camera the other way, so the view follows the fingers.
+        let mut angle = controls.direction(&keyboard_buttons) * controls.speed * time.delta_secs();
+
+        if !has_cursor || hovered_cameras.get(&window_entity) == Some(&entity) {
+            angle -= gesture * controls.gesture_sensitivity;
+        }
+
+        if let Some((touch_pinch, touch_pinch_camera)) = touch_pinch
+            && pan_cam.touch_controls
+            && touch_pinch_camera == entity
+        {
+            angle -= touch_pinch.rotation * controls.gesture_sensitivity;
+        }
+
+        let viewport = camera
+            .logical_viewport_rect()
+            .unwrap_or(Rect::from_corners(Vec2::ZERO, window.size()));
+
+        if drag_state.grabbed
+            && drag_state.touch_id.is_none()
+            && controls.drag_rotates(&keyboard_buttons)
+            && let Some(cursor_pos) = window.cursor_position()
+        {
+            // Turn the view by the angle the cursor moved around the middle
+            // of the viewport
+            let from_center = |pos: Vec2| {
+                let offset = pos - viewport.center();
+                vec2(offset.x, -offset.y)
+            };
+            if let Some(last_pos) = last_drag_pos.insert(entity, cursor_pos) {
+                let drag_angle = from_center(last_pos).angle_to(from_center(cursor_pos));
+                // The angle is undefined right at the middle
+                if drag_angle.is_finite() {
+                    angle -= drag_angle;
+                }
+            }
+        } else {
+            last_drag_pos.remove(&entity);
+        }
+
+        if angle == 0. {
+            continue;
+        }
+
+        transform.rotate_z(angle);
+
+        // The rotated view may no longer fit the bounds
+        let prev_scale = proj.scale;
+        constrain_proj_scale(proj, pan_cam, viewport.size(), transform.rotation);
+        if let Some(target_scale) = &mut zoom_state.target_scale {
+            *target_scale = target_scale.min(proj.scale);
+        }
+        if proj.scale != prev_scale {
+            commands.trigger(PanCamZoomed {
+                entity,
+                old_scale: prev_scale,
+                new_scale: proj.scale,
+            });
+        }
+
+        let area_size = proj.area.size() / prev_scale * proj.scale;
+        let new_cam_pos = clamp_to_safe_zone(
+            snap_state.position(transform.translation.truncate()),
+            pan_cam,
+            view_extent(area_size, transform.rotation),
+        );
+
+        // Pixels line up differently in the rotated view
+        let pixel_size = area_size / viewport.size() / window.scale_factor();
+        let previous_translation = transform.translation.truncate();
+        transform.translation = snap_state
+            .apply(new_cam_pos, pan_cam, pixel_size, transform.rotation)
+            .extend(transform.translation.z);
+        trigger_moved(&mut commands, entity, previous_translation, &transform);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_6};
+
+    use bevy::input::{ButtonState, gestures::RotationGesture};
+
+    use super::*;
+    use crate::{
+        do_camera_movement, do_camera_zoom,
+        test_utils::{input_app, mock_proj, mouse_button, move_cursor, scroll},
+    };
+
+    /// Spawns a `PanCam` camera turned a quarter counterclockwise
+    fn spawn_rotated_camera(app: &mut App) -> Entity {
+        app.world_mut()
+            .spawn((
+                PanCam {
+                    rotation: Some(RotationControls::default()),
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+                Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2)),
+            ))
+            .id()
+    }
+
+    fn angle(app: &App, camera: Entity) -> f32 {
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        transform.rotation.to_euler(EulerRot::XYZ).2
+    }
+
+    #[test]
+    fn dragging_a_rotated_view_moves_the_content_with_the_cursor() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_movement);
+        let camera = spawn_rotated_camera(&mut app);
+
+        move_cursor(&mut app, Some(vec2(50., 50.)));
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Pressed);
+        app.update();
+        move_cursor(&mut app, Some(vec2(60., 50.)));
+        app.update();
+
+        // The view's right is the world's up, so the world point that was in
+        // the middle is now 10 pixels right of it, under the cursor
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert!(transform.translation.abs_diff_eq(vec3(0., -10., 0.), 0.001));
+    }
+
+    #[test]
+    fn zooming_a_rotated_view_keeps_the_point_under_the_cursor() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_zoom);
+        let camera = spawn_rotated_camera(&mut app);
+
+        // 25 pixels right of the middle is 25 units up in the world
+        move_cursor(&mut app, Some(vec2(75., 50.)));
+        scroll(&mut app);
+        app.update();
+
+        let Projection::Orthographic(proj) = app.world().get::<Projection>(camera).unwrap() else {
+            unreachable!();
+        };
+        assert!(proj.scale < 1.);
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        let under_cursor = transform.translation.truncate()
+            + (transform.rotation * vec3(25. * proj.scale, 0., 0.)).truncate();
+        assert!(under_cursor.abs_diff_eq(vec2(0., 25.), 0.001));
+    }
+
+    #[test]
+    fn rotation_keys_turn_the_camera() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_rotation);
+        let camera = spawn_rotated_camera(&mut app);
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyQ);
+        app.update();
+        assert!((angle(&app, camera) - (FRAC_PI_2 + 0.2)).abs() < 0.001);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyE);
+        app.update();
+        assert!((angle(&app, camera) - (FRAC_PI_2 + 0.2)).abs() < 0.001);
+    }
+
+    #[test]
+    fn dragging_with_a_modifier_turns_the_view_with_the_cursor() {
+        let mut app = input_app();
+        app.add_systems(Update, (do_camera_rotation, do_camera_movement).chain());
+        let camera = spawn_rotated_camera(&mut app);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::AltLeft);
+        move_cursor(&mut app, Some(vec2(75., 50.)));
+        mouse_button(&mut app, MouseButton::Left, ButtonState::Pressed);
+        app.update();
+        app.update();
+
+        // Moving the cursor a quarter turn counterclockwise around the middle
+        // turns the view along, so the camera turns clockwise
+        move_cursor(&mut app, Some(vec2(50., 25.)));
+        app.update();
+        assert!(angle(&app, camera).abs() < 0.001);
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn rotation_gestures_turn_the_view_with_the_fingers() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_rotation);
+        let camera = spawn_rotated_camera(&mut app);
+
+        app.world_mut().write_message(RotationGesture(30.));
+        app.update();
+        assert!((angle(&app, camera) - (FRAC_PI_2 - FRAC_PI_6)).abs() < 0.001);
+    }
+}
@@ -0,0 +1,460 @@
+use std::{f32::consts::TAU, sync::Arc};
+
+use bevy::prelude::*;
+
+/// A shape that a `PanCam` camera is kept inside, see
+/// [`PanCam::shape_bounds`](crate::PanCam::shape_bounds)
+///
+/// Implement this for custom shapes. Only
+/// [`closest_point`](BoundsShape::closest_point) and
+/// [`bounding_rect`](BoundsShape::bounding_rect) are required; the default
+/// [`clamp_view`](BoundsShape::clamp_view) works for convex shapes but may
+/// leave the view slightly outside of them.
+pub trait BoundsShape: Send + Sync + 'static {
+    /// Returns the point of the shape closest to `point`, which is `point`
+    /// itself when it's inside the shape
+    fn closest_point(&self, point: Vec2) -> Vec2;
+
+    /// Returns the smallest world aligned rectangle around the shape
+    fn bounding_rect(&self) -> Rect;
+
+    /// Returns the camera position closest to `pos` at which a view reaching
+    /// `half_size` from the camera on each axis lies inside the shape
+    ///
+    /// The default repeatedly pushes the view corners back inside the shape.
+    fn clamp_view(&self, pos: Vec2, half_size: Vec2) -> Vec2 {
+        let mut pos = pos;
+        for _ in 0..16 {
+            let mut moved = false;
+            for corner in corners(half_size) {
+                let point = pos + corner;
+                let inside = self.closest_point(point);
+                if inside != point {
+                    pos += inside - point;
+                    moved = true;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+        pos
+    }
+}
+
+/// What part of the view [`ShapeBounds`] keep inside their shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum KeepInside {
+    /// The camera position, in the middle of the view
+    Center,
+    /// The whole view
+    ///
+    /// Zooming out is limited by the [`BoundsShape::bounding_rect`] of the
+    /// shape. Where the view doesn't fit the shape, only the camera position
+    /// is kept inside.
+    #[default]
+    WholeView,
+}
+
+/// Bounds of any shape for a `PanCam` camera, see
+/// [`PanCam::shape_bounds`](crate::PanCam::shape_bounds)
+#[derive(Clone)]
+pub struct ShapeBounds {
+    /// The shape the camera is kept inside
+    pub shape: Arc<dyn BoundsShape>,
+    /// What part of the view is kept inside the shape
+    pub keep_inside: KeepInside,
+}
+
+impl ShapeBounds {
+    /// Keeps the whole view inside `shape`
+    pub fn new(shape: impl BoundsShape) -> Self {
+        Self {
+            shape: Arc::new(shape),
+            keep_inside: KeepInside::WholeView,
+        }
+    }
+
+    /// Keeps only the camera position inside `shape`
+    pub fn center(shape: impl BoundsShape) -> Self {
+        Self {
+            shape: Arc::new(shape),
+            keep_inside: KeepInside::Center,
+        }
+    }
+
+    /// Clamps a camera position for a view of the given world aligned size
+    pub(crate) fn clamp(&self, pos: Vec2, view_size: Vec2) -> Vec2 {
+        match self.keep_inside {
+            KeepInside::Center => self.shape.closest_point(pos),
+            KeepInside::WholeView => self.shape.clamp_view(pos, view_size / 2.),
+        }
+    }
+
+    /// Returns the size of the shape that limits zooming out
+    pub(crate) fn zoom_bounds_size(&self) -> Vec2 {
+        match self.keep_inside {
+            KeepInside::Center => Vec2::INFINITY,
+            KeepInside::WholeView => self.shape.bounding_rect().size(),
+        }
+    }
+}
+
+/// A convex polygon to keep a `PanCam` camera inside, e.g. a hex region
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexPolygonBounds {
+    /// The corners in counterclockwise order
+    vertices: Vec<Vec2>,
+}
+
+impl ConvexPolygonBounds {
+    /// Creates a polygon from its corners, in either winding order
+    ///
+    /// # Panics
+    ///
+    /// Panics with fewer than three vertices, or if the polygon isn't convex.
+    pub fn new(vertices: impl IntoIterator<Item = Vec2>) -> Self {
+        let mut vertices: Vec<Vec2> = vertices.into_iter().collect();
+        assert!(
+            vertices.len() >= 3,
+            "a polygon needs at least three vertices"
+        );
+
+        // Every corner turns the same way, or goes straight on
+        let count = vertices.len();
+        let turns: Vec<f32> = (0..count)
+            .map(|i| {
+                let (a, b, c) = (
+                    vertices[i],
+                    vertices[(i + 1) % count],
+                    vertices[(i + 2) % count],
+                );
+                (b - a).perp_dot(c - b)
+            })
+            .collect();
+        // and the edges go around only once, unlike e.g. in a star
+        let winding: f32 = (0..count)
+            .map(|i| {
+                let (a, b, c) = (
+                    vertices[i],
+                    vertices[(i + 1) % count],
+                    vertices[(i + 2) % count],
+                );
+                f32::atan2((b - a).perp_dot(c - b), (b - a).dot(c - b))
+            })
+            .sum();
+        assert!(
+            (turns.iter().all(|turn| *turn >= 0.) || turns.iter().all(|turn| *turn <= 0.))
+                && (winding.abs() - TAU).abs() < 0.001,
+            "the polygon needs to be convex"
+        );
+
+        let doubled_area: f32 = edges(&vertices).map(|(a, b)| a.perp_dot(b)).sum();
+        if doubled_area < 0. {
+            vertices.reverse();
+        }
+        Self { vertices }
+    }
+
+    /// The corners in counterclockwise order
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    /// The edges as `(outward normal, distance)` half-planes, where points
+    /// inside have `normal.dot(point) <= distance`
+    fn half_planes(&self) -> impl Iterator<Item = (Vec2, f32)> + '_ {
+        edges(&self.vertices).filter_map(|(a, b)| {
+            let normal = -(b - a).perp().normalize_or_zero();
+            (normal != Vec2::ZERO).then(|| (normal, normal.dot(a)))
+        })
+    }
+}
+
+impl BoundsShape for ConvexPolygonBounds {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        if self
+            .half_planes()
+            .all(|(normal, distance)| normal.dot(point) <= distance)
+        {
+            return point;
+        }
+
+        edges(&self.vertices)
+            .map(|(a, b)| closest_point_on_segment(point, a, b))
+            .min_by(|a, b| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+            .unwrap_or(point)
+    }
+
+    fn bounding_rect(&self) -> Rect {
+        self.vertices
+            .iter()
+            .fold(Rect::EMPTY, |rect, vertex| rect.union_point(*vertex))
+    }
+
+    fn clamp_view(&self, pos: Vec2, half_size: Vec2) -> Vec2 {
+        // The positions that keep the view inside are the polygon with every
+        // edge moved inwards until the view touches it
+        let half_planes: Vec<(Vec2, f32)> = self
+            .half_planes()
+            .map(|(normal, distance)| (normal, distance - normal.abs().dot(half_size)))
+            .collect();
+        let tolerance = 1e-4 * half_size.max_element().max(1.);
+        let inside = |point: Vec2| {
+            half_planes
+                .iter()
+                .all(|(normal, distance)| normal.dot(point) <= distance + tolerance)
+        };
+
+        if inside(pos) {
+            return pos;
+        }
+
+        // Otherwise the closest position is on one of the moved edges, or
+        // where two of them meet
+        let on_edges = half_planes
+            .iter()
+            .map(|(normal, distance)| pos - (normal.dot(pos) - distance) * *normal);
+        let on_corners = half_planes.iter().enumerate().flat_map(|(i, a)| {
+            half_planes[i + 1..]
+                .iter()
+                .filter_map(move |b| line_intersection(*a, *b))
+        });
+
+        on_edges
+            .chain(on_corners)
+            .filter(|point| inside(*point))
+            .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+            // The view doesn't fit the polygon
+            .unwrap_or_else(|| self.closest_point(pos))
+    }
+}
+
+/// A circle to keep a `PanCam` camera inside, e.g. an island
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct CircleBounds {
+    /// The middle of the circle
+    pub center: Vec2,
+    /// The radius of the circle
+    pub radius: f32,
+}
+
+impl CircleBounds {
+    /// Creates a circle around `center`
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl BoundsShape for CircleBounds {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        self.center + (point - self.center).clamp_length_max(self.radius)
+    }
+
+    fn bounding_rect(&self) -> Rect {
+        Rect::from_center_half_size(self.center, Vec2::splat(self.radius))
+    }
+
+    fn clamp_view(&self, pos: Vec2, half_size: Vec2) -> Vec2 {
+        // Each view corner has to stay inside the circle, so the camera has to
+        // stay inside a circle moved against each corner
+        let centers = corners(half_size).map(|corner| self.center - corner);
+        let radius = self.radius;
+        let tolerance = 1e-4 * radius.max(1.);
+        let inside = |point: Vec2| {
+            centers
+                .iter()
+                .all(|center| point.distance(*center) <= radius + tolerance)
+        };
+
+        if inside(pos) {
+            return pos;
+        }
+
+        // Otherwise the closest position is on one of the moved circles, or
+        // where two of them cross
+        let on_circles = centers
+            .iter()
+            .map(|center| *center + (pos - *center).normalize_or_zero() * radius);
+        let on_crossings = centers.iter().enumerate().flat_map(|(i, a)| {
+            centers[i + 1..]
+                .iter()
+                .flat_map(move |b| circle_crossings(*a, *b, radius).into_iter().flatten())
+        });
+
+        on_circles
+            .chain(on_crossings)
+            .filter(|point| inside(*point))
+            .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+            // The view doesn't fit the circle
+            .unwrap_or(self.center)
+    }
+}
+
+/// Returns the offsets of the view corners from the camera position
+fn corners(half_size: Vec2) -> [Vec2; 4] {
+    [
+        vec2(-half_size.x, -half_size.y),
+        vec2(half_size.x, -half_size.y),
+        vec2(half_size.x, half_size.y),
+        vec2(-half_size.x, half_size.y),
+    ]
+}
+
+/// Returns the edges of a polygon as pairs of consecutive vertices
+fn edges(vertices: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+fn closest_point_on_segment(point: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared == 0. {
+        return a;
+    }
+    a + ab * ((point - a).dot(ab) / length_squared).clamp(0., 1.)
+}
+
+/// Returns where the boundary lines of two half-planes cross, unless they are
+/// parallel
+fn line_intersection((n1, d1): (Vec2, f32), (n2, d2): (Vec2, f32)) -> Option<Vec2> {
+    let det = n1.perp_dot(n2);
+    if det.abs() < 1e-6 {
+        return None;
+    }
+    Some(vec2(d1 * n2.y - d2 * n1.y, n1.x * d2 - n2.x * d1) / det)
+}
+
+/// Returns where two circles of the same radius cross
+fn circle_crossings(a: Vec2, b: Vec2, radius: f32) -> Option<[Vec2; 2]> {
+    let half_distance = a.distance(b) / 2.;
+    if half_distance == 0. || half_distance > radius {
+        return None;
+    }
+    let middle = a.midpoint(b);
+    let offset =
+        (b - a).perp().normalize() * (radius * radius - half_distance * half_distance).sqrt();
+    Some([middle + offset, middle - offset])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use crate::{BoundsContact, PanCam, clamp_to_safe_zone, state};
+
+    use super::*;
+
+    #[test]
+    fn polygon_bounds_keep_the_view_inside() {
+        let diamond = ConvexPolygonBounds::new([
+            vec2(0., 100.),
+            vec2(100., 0.),
+            vec2(0., -100.),
+            vec2(-100., 0.),
+        ]);
+        let pan_cam = PanCam {
+            shape_bounds: Some(ShapeBounds::new(diamond)),
+            ..default()
+        };
+        let view_size = vec2(20., 20.);
+
+        assert_eq!(
+            clamp_to_safe_zone(vec2(10., 20.), &pan_cam, view_size),
+            vec2(10., 20.)
+        );
+        // The top right corner of the view touches the top right edge
+        assert!(
+            clamp_to_safe_zone(vec2(100., 100.), &pan_cam, view_size)
+                .abs_diff_eq(vec2(40., 40.), 0.001)
+        );
+        // The right corner of the view touches the right corner of the diamond
+        assert!(
+            clamp_to_safe_zone(vec2(200., 0.), &pan_cam, view_size)
+                .abs_diff_eq(vec2(80., 0.), 0.001)
+        );
+        assert_eq!(pan_cam.zoom_bounds_size(), vec2(200., 200.));
+
+        // Against the top right edge, the view can't go further right or up
+        let contact = state::bounds_contact(
+            clamp_to_safe_zone(vec2(100., 100.), &pan_cam, view_size),
+            &pan_cam,
+            view_size,
+        );
+        assert_eq!(
+            contact,
+            BoundsContact {
+                max_x: true,
+                max_y: true,
+                ..default()
+            }
+        );
+        assert!(!state::bounds_contact(Vec2::ZERO, &pan_cam, view_size).any());
+    }
+
+    #[test]
+    fn polygon_bounds_need_to_be_convex() {
+        let concave = vec![vec2(0., 0.), vec2(100., 0.), vec2(10., 10.), vec2(0., 100.)];
+        // A star turns the same way at every corner, but goes around twice
+        let pentagram = (0..5)
+            .map(|i| Vec2::from_angle(i as f32 * 4. * PI / 5.) * 100.)
+            .collect();
+
+        for vertices in [concave, pentagram] {
+            let panic = std::panic::catch_unwind(|| ConvexPolygonBounds::new(vertices))
+                .expect_err("the polygon isn't convex");
+            assert!(panic.downcast_ref::<&str>().unwrap().contains("convex"));
+        }
+    }
+
+    #[test]
+    fn circle_bounds_keep_the_view_or_center_inside() {
+        let circle = CircleBounds::new(Vec2::ZERO, 100.);
+        let view_size = vec2(60., 80.);
+
+        // Both right corners of the view touch the circle
+        let whole_view = ShapeBounds::new(circle);
+        let expected_x = (100_f32.powi(2) - 40_f32.powi(2)).sqrt() - 30.;
+        assert!(
+            whole_view
+                .clamp(vec2(200., 0.), view_size)
+                .abs_diff_eq(vec2(expected_x, 0.), 0.001)
+        );
+
+        let center = ShapeBounds::center(circle);
+        assert_eq!(center.clamp(vec2(200., 0.), view_size), vec2(100., 0.));
+        assert_eq!(center.zoom_bounds_size(), Vec2::INFINITY);
+    }
+
+    #[test]
+    fn custom_bounds_shapes_push_the_view_inside() {
+        struct Island;
+
+        impl BoundsShape for Island {
+            fn closest_point(&self, point: Vec2) -> Vec2 {
+                point.clamp_length_max(100.)
+            }
+
+            fn bounding_rect(&self) -> Rect {
+                Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(100.))
+            }
+        }
+
+        let half_size = vec2(30., 40.);
+        let pos = Island.clamp_view(vec2(200., 50.), half_size);
+        for corner in [
+            half_size,
+            -half_size,
+            vec2(half_size.x, -half_size.y),
+            vec2(-half_size.x, half_size.y),
+        ] {
+            assert!((pos + corner).length() < 100.5);
+        }
+    }
+}
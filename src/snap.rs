@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::PanCam;
+
+/// Per-camera state for pixel snapping, see [`PanCam::pixel_snap`](crate::PanCam::pixel_snap)
+#[derive(Component, Default)]
+pub(crate) struct SnapState {
+    /// The last snapped position written to the transform, and the precise
+    /// position it was snapped from
+    last: Option<(Vec2, Vec2)>,
+}
+
+impl SnapState {
+    /// Returns the precise camera position, unless something else moved the
+    /// camera since it was last snapped
+    pub(crate) fn position(&self, translation: Vec2) -> Vec2 {
+        match self.last {
+            Some((snapped, precise)) if snapped == translation => precise,
+            _ => translation,
+        }
+    }
+
+    /// Returns the position to write to the transform for a precise camera
+    /// position, snapped to whole physical pixels if the camera asks for it
+    pub(crate) fn apply(
+        &mut self,
+        precise: Vec2,
+        pan_cam: &PanCam,
+        pixel_size: Vec2,
+        rotation: Quat,
+    ) -> Vec2 {
+        if !pan_cam.pixel_snap || !pixel_size.is_finite() || pixel_size.min_element() <= 0. {
+            self.last = None;
+            return precise;
+        }
+
+        let snapped = snap_to_pixels(precise, pixel_size, rotation);
+        self.last = Some((snapped, precise));
+        snapped
+    }
+
+    /// Moves the last snapped position along with a camera that wrapped
+    /// around the world
+    pub(crate) fn shift(&mut self, offset: Vec2) {
+        if let Some((snapped, precise)) = &mut self.last {
+            *snapped += offset;
+            *precise += offset;
+        }
+    }
+}
+
+/// `snap_to_pixels` rounds a camera position to whole pixels of the given
+/// world size, along the axes of the possibly rotated view.
+fn snap_to_pixels(pos: Vec2, pixel_size: Vec2, rotation: Quat) -> Vec2 {
+    let view_pos = (rotation.inverse() * pos.extend(0.)).truncate();
+    let snapped = (view_pos / pixel_size).round() * pixel_size;
+    (rotation * snapped.extend(0.)).truncate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixel_snapping_rounds_to_whole_pixels() {
+        let pixel_size = Vec2::splat(0.5);
+        assert_eq!(
+            snap_to_pixels(vec2(1.2, -0.3), pixel_size, Quat::IDENTITY),
+            vec2(1., -0.5)
+        );
+
+        let mut snap_state = SnapState::default();
+        let pan_cam = PanCam {
+            pixel_snap: true,
+            ..default()
+        };
+        let snapped = snap_state.apply(vec2(1.2, 0.), &pan_cam, pixel_size, Quat::IDENTITY);
+        assert_eq!(snapped, vec2(1., 0.));
+        assert_eq!(snap_state.position(snapped), vec2(1.2, 0.));
+        assert_eq!(snap_state.position(vec2(3., 0.)), vec2(3., 0.));
+    }
+}
@@ -0,0 +1,222 @@
+use bevy::{
+    camera::{CameraProjection, RenderTarget},
+    prelude::*,
+};
+
+use crate::{DragState, PanCam, PanCamWindows, safe_zone, view_extent};
+
+/// Read-only runtime state of a `PanCam` camera, added automatically next to
+/// it
+///
+/// The state is updated at the end of [`PanCamSystems`](crate::PanCamSystems),
+/// so order systems that read it `.after(PanCamSystems)`.
+#[derive(Component, Reflect, Debug, Default, Clone)]
+#[reflect(Component)]
+pub struct PanCamState {
+    dragging: bool,
+    grab_button: Option<MouseButton>,
+    cursor_world_position: Option<Vec2>,
+    velocity: Vec2,
+    bounds_contact: BoundsContact,
+    last_translation: Option<Vec2>,
+}
+
+impl PanCamState {
+    /// Whether the user is dragging the camera, by mouse or by touch
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// The mouse button the camera is being dragged with, or `None` when it
+    /// isn't dragged or is dragged by touch
+    pub fn grab_button(&self) -> Option<MouseButton> {
+        self.grab_button
+    }
+
+    /// The world position of the cursor the last time it was over the
+    /// viewport of the camera
+    pub fn cursor_world_position(&self) -> Option<Vec2> {
+        self.cursor_world_position
+    }
+
+    /// How fast the camera moved during the last frame, in world units per
+    /// second
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    /// Which bounds the edges of the view are against
+    pub fn bounds_contact(&self) -> BoundsContact {
+        self.bounds_contact
+    }
+
+    /// Moves the last position along with a camera that wrapped around the
+    /// world, so the jump doesn't count as velocity
+    pub(crate) fn shift(&mut self, offset: Vec2) {
+        if let Some(last_translation) = &mut self.last_translation {
+            *last_translation += offset;
+        }
+    }
+}
+
+/// Which bounds of a `PanCam` camera the edges of the view are against
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub struct BoundsContact {
+    /// Whether the view is against [`PanCam::min_x`]
+    pub min_x: bool,
+    /// Whether the view is against [`PanCam::max_x`]
+    pub max_x: bool,
+    /// Whether the view is against [`PanCam::min_y`]
+    pub min_y: bool,
+    /// Whether the view is against [`PanCam::max_y`]
+    pub max_y: bool,
+}
+
+impl BoundsContact {
+    /// Whether the view is against any of the bounds
+    pub fn any(&self) -> bool {
+        self.min_x || self.max_x || self.min_y || self.max_y
+    }
+}
+
+pub(crate) fn update_pan_cam_state(
+    query: Query<(
+        Entity,
+        &PanCam,
+        &Camera,
+        &RenderTarget,
+        &Transform,
+        &Projection,
+    )>,
+    mut states: Query<(&DragState, &mut PanCamState)>,
+    windows: PanCamWindows,
+    time: Res<Time<Real>>,
+) {
+    for (entity, pan_cam, camera, render_target, transform, projection) in &query {
+        let Ok((drag_state, mut state)) = states.get_mut(entity) else {
+            continue;
+        };
+
+        state.dragging = drag_state.grabbed;
+        state.grab_button = drag_state.grab_button;
+
+        let translation = transform.translation.truncate();
+        let delta_secs = time.delta_secs();
+        state.velocity = match state.last_translation {
+            Some(last) if delta_secs > 0. => (translation - last) / delta_secs,
+            _ => Vec2::ZERO,
+        };
+        state.last_translation = Some(translation);
+
+        let Projection::Orthographic(proj) = projection else {
+            continue;
+        };
+        let Some((_, window)) = windows.camera_window(render_target) else {
+            continue;
+        };
+
+        let viewport = camera
+            .logical_viewport_rect()
+            .unwrap_or(Rect::from_corners(Vec2::ZERO, window.size()));
+
+        // The projection area is only refreshed after `PanCamSystems`, so
+        // compute it for the current scale
+        let mut proj = proj.clone();
+        proj.update(viewport.width(), viewport.height());
+
+        if let Some(cursor_pos) = window.cursor_position()
+            && viewport.contains(cursor_pos)
+        {
+            state.cursor_world_position =
+                Some(view_to_world(cursor_pos, viewport, proj.area, transform));
+        }
+
+        state.bounds_contact = bounds_contact(
+            translation,
+            pan_cam,
+            view_extent(proj.area.size(), transform.rotation),
+        );
+    }
+}
+
+/// `view_to_world` converts a logical position in a viewport to the world
+/// position it shows.
+fn view_to_world(pos: Vec2, viewport: Rect, area: Rect, transform: &Transform) -> Vec2 {
+    // Window coordinates grow downwards, world coordinates upwards
+    let fraction = (pos - viewport.min) / viewport.size();
+    let local = vec2(
+        area.min.x + fraction.x * area.width(),
+        area.max.y - fraction.y * area.height(),
+    );
+    transform.translation.truncate() + (transform.rotation * local.extend(0.)).truncate()
+}
+
+/// `bounds_contact` finds which bounds a view of the given world aligned size
+/// touches with the camera at `pos`.
+pub(crate) fn bounds_contact(
+    pos: Vec2,
+    pan_cam: &PanCam,
+    bounded_view_size: Vec2,
+) -> BoundsContact {
+    // A view bigger than the bounds is against both of them
+    let bounded_view_size = pan_cam.bounds_padding.inner_view_size(bounded_view_size);
+    let (min, max) = safe_zone(pan_cam.aabb(), bounded_view_size);
+    // Leave some room for floating point error in the clamped position
+    let tolerance = bounded_view_size * 1e-4;
+
+    // The view is against a shape where nudging the camera outwards gets it
+    // pushed back
+    let against_shape = |direction: Vec2| {
+        pan_cam.shape_bounds.as_ref().is_some_and(|shape_bounds| {
+            let nudged = pos + direction * tolerance;
+            let push = shape_bounds.clamp(nudged, bounded_view_size) - nudged;
+            push.dot(direction) < 0.
+        })
+    };
+
+    // Wrapping axes have no edges to be against
+    let (wrap_x, wrap_y) = (pan_cam.wrap_x, pan_cam.wrap_y);
+    BoundsContact {
+        min_x: !wrap_x && (pos.x <= min.x + tolerance.x || against_shape(Vec2::NEG_X)),
+        max_x: !wrap_x && (pos.x >= max.x - tolerance.x || against_shape(Vec2::X)),
+        min_y: !wrap_y && (pos.y <= min.y + tolerance.y || against_shape(Vec2::NEG_Y)),
+        max_y: !wrap_y && (pos.y >= max.y - tolerance.y || against_shape(Vec2::Y)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_contact_reports_the_touched_edges() {
+        let pan_cam = PanCam {
+            min_x: -100.,
+            max_x: 100.,
+            min_y: -100.,
+            max_y: 100.,
+            ..default()
+        };
+        let view_size = vec2(100., 100.);
+
+        assert_eq!(
+            bounds_contact(vec2(50., 0.), &pan_cam, view_size),
+            BoundsContact {
+                max_x: true,
+                ..default()
+            }
+        );
+        assert_eq!(
+            bounds_contact(vec2(-50., -50.), &pan_cam, view_size),
+            BoundsContact {
+                min_x: true,
+                min_y: true,
+                ..default()
+            }
+        );
+        assert!(!bounds_contact(Vec2::ZERO, &pan_cam, view_size).any());
+
+        let unbounded = PanCam::default();
+        assert!(!bounds_contact(vec2(1e6, 0.), &unbounded, view_size).any());
+    }
+}
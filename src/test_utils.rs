@@ -0,0 +1,123 @@
+//! Helpers shared by the tests of the different modules
+
+use std::time::Duration;
+
+use bevy::{
+    camera::{CameraProjection, ComputedCameraValues, RenderTargetInfo, Viewport},
+    input::{
+        ButtonState, InputPlugin,
+        mouse::{MouseButtonInput, MouseScrollUnit, MouseWheel},
+        touch::{TouchInput, TouchPhase},
+    },
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::touch::TouchWindows;
+
+/// Simple mock function to construct a square projection from a window size
+pub(crate) fn mock_proj(window_size: Vec2) -> OrthographicProjection {
+    let mut proj = OrthographicProjection::default_2d();
+    proj.update(window_size.x, window_size.y);
+    proj
+}
+
+/// Builds an app with real input handling, a 100x100 primary window and
+/// 100ms frames
+pub(crate) fn input_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(InputPlugin)
+        .init_resource::<Time<Real>>()
+        .init_resource::<TouchWindows>()
+        .add_systems(First, |mut time: ResMut<Time<Real>>| {
+            time.update_with_duration(Duration::from_millis(100));
+        });
+    app.world_mut().spawn((
+        Window {
+            resolution: (100, 100).into(),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app
+}
+
+pub(crate) fn primary_window(app: &mut App) -> Entity {
+    app.world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world())
+        .unwrap()
+}
+
+/// Moves the cursor within the primary window of an [`input_app`]
+pub(crate) fn move_cursor(app: &mut App, position: Option<Vec2>) {
+    let window = primary_window(app);
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .set_cursor_position(position);
+}
+
+/// Presses or releases a mouse button in the primary window of an
+/// [`input_app`]
+pub(crate) fn mouse_button(app: &mut App, button: MouseButton, state: ButtonState) {
+    let window = primary_window(app);
+    app.world_mut().write_message(MouseButtonInput {
+        button,
+        state,
+        window,
+    });
+}
+
+/// Sends a touch event to the primary window of an [`input_app`]
+pub(crate) fn touch(app: &mut App, id: u64, phase: TouchPhase, position: Vec2) {
+    let window = primary_window(app);
+    app.world_mut().write_message(TouchInput {
+        phase,
+        position,
+        window,
+        force: None,
+        id,
+    });
+}
+
+/// Scrolls one line up in the primary window of an [`input_app`]
+pub(crate) fn scroll(app: &mut App) {
+    let window = primary_window(app);
+    app.world_mut().write_message(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.,
+        y: 1.,
+        window,
+        phase: TouchPhase::Moved,
+    });
+}
+
+/// A camera drawing to a full height part of a 100x100 window, as if the
+/// renderer had already computed its viewport
+pub(crate) fn viewport_camera(min_x: u32, width: u32, order: isize) -> Camera {
+    Camera {
+        viewport: Some(Viewport {
+            physical_position: uvec2(min_x, 0),
+            physical_size: uvec2(width, 100),
+            ..default()
+        }),
+        order,
+        computed: ComputedCameraValues {
+            target_info: Some(RenderTargetInfo {
+                physical_size: uvec2(100, 100),
+                scale_factor: 1.,
+            }),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+/// Returns the projection scale of a camera
+pub(crate) fn scale(app: &App, camera: Entity) -> f32 {
+    let Projection::Orthographic(proj) = app.world().get::<Projection>(camera).unwrap() else {
+        unreachable!();
+    };
+    proj.scale
+}
@@ -0,0 +1,223 @@
+use bevy::{
+    camera::RenderTarget,
+    ecs::system::SystemParam,
+    input::touch::{Touch, TouchInput, TouchPhase},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{DragState, PanCam, hovered_cameras};
+
+/// The window each active touch started in, which [`Touches`] doesn't keep
+/// track of
+#[derive(Resource, Default)]
+pub(crate) struct TouchWindows {
+    windows: HashMap<u64, Entity>,
+    /// Whether any touch changed this frame
+    ///
+    /// [`Touches`] only moves the previous positions forward on frames with
+    /// touch events, so resting fingers would otherwise repeat their last
+    /// movement.
+    changed: bool,
+}
+
+/// The touch state needed to drive `PanCam` cameras
+#[derive(SystemParam)]
+pub(crate) struct TouchInputs<'w> {
+    pub touches: Res<'w, Touches>,
+    touch_windows: Res<'w, TouchWindows>,
+}
+
+impl TouchInputs<'_> {
+    /// Returns the window a pressed touch started in
+    pub(crate) fn window(&self, id: u64) -> Option<Entity> {
+        self.touch_windows.windows.get(&id).copied()
+    }
+
+    /// Returns the finger that just touched down, if it's the only one, and
+    /// the camera under it for it to grab
+    pub(crate) fn grab<'a>(
+        &self,
+        primary_window: Option<Entity>,
+        cameras: impl Iterator<Item = (Entity, &'a PanCam, &'a Camera, &'a RenderTarget)>,
+    ) -> Option<(u64, Entity)> {
+        let touch = self
+            .touches
+            .iter_just_pressed()
+            .next()
+            .filter(|_| self.touches.iter().count() == 1)?;
+        let window = self.window(touch.id())?;
+        let camera = hovered_cameras(
+            &HashMap::from_iter([(window, touch.position())]),
+            primary_window,
+            cameras,
+        )
+        .get(&window)
+        .copied()?;
+        Some((touch.id(), camera))
+    }
+
+    /// Continues the touch drag of a camera, returning how far the finger
+    /// moved this frame in logical pixels with y growing upwards
+    ///
+    /// A second finger turns the drag into a pinch, see `do_camera_zoom`.
+    pub(crate) fn drag(&self, drag_state: &mut DragState) -> Option<Vec2> {
+        let touch_id = drag_state.touch_id?;
+        match self.touches.get_pressed(touch_id) {
+            Some(touch) if self.touches.iter().count() == 1 => {
+                let delta = self.delta(touch);
+                Some(vec2(delta.x, -delta.y))
+            }
+            _ if self.touches.iter().count() > 1 => {
+                drag_state.stop();
+                None
+            }
+            _ => {
+                drag_state.touch_id = None;
+                drag_state.grabbed = false;
+                None
+            }
+        }
+    }
+
+    /// Returns the pinch gesture made by the pressed touches and the camera
+    /// under the point between the fingers, which it goes to
+    pub(crate) fn routed_pinch<'a>(
+        &self,
+        primary_window: Option<Entity>,
+        cameras: impl Iterator<Item = (Entity, &'a PanCam, &'a Camera, &'a RenderTarget)>,
+    ) -> Option<(TouchPinch, Entity)> {
+        let pinch = self.pinch()?;
+        let camera = hovered_cameras(
+            &HashMap::from_iter([(pinch.window, pinch.midpoint)]),
+            primary_window,
+            cameras,
+        )
+        .get(&pinch.window)
+        .copied()?;
+        Some((pinch, camera))
+    }
+
+    /// Returns how far a touch moved this frame
+    fn delta(&self, touch: &Touch) -> Vec2 {
+        touch.position() - self.previous_position(touch)
+    }
+
+    fn previous_position(&self, touch: &Touch) -> Vec2 {
+        if self.touch_windows.changed {
+            touch.previous_position()
+        } else {
+            touch.position()
+        }
+    }
+
+    /// Returns the pinch gesture made by the pressed touches, if exactly two
+    /// fingers are down
+    fn pinch(&self) -> Option<TouchPinch> {
+        let mut pressed = self.touches.iter();
+        let (Some(a), Some(b), None) = (pressed.next(), pressed.next(), pressed.next()) else {
+            return None;
+        };
+
+        let window = self.window(a.id())?;
+        let (previous_a, previous_b) = (self.previous_position(a), self.previous_position(b));
+        let previous_distance = previous_a.distance(previous_b);
+        let distance = a.position().distance(b.position());
+        if previous_distance <= 0. || distance <= 0. {
+            return None;
+        }
+
+        Some(TouchPinch {
+            window,
+            previous_midpoint: previous_a.midpoint(previous_b),
+            midpoint: a.position().midpoint(b.position()),
+            scale_factor: previous_distance / distance,
+            rotation: y_up(previous_a - previous_b).angle_to(y_up(a.position() - b.position())),
+        })
+    }
+}
+
+pub(crate) fn track_touch_windows(
+    mut touch_events: MessageReader<TouchInput>,
+    mut touch_windows: ResMut<TouchWindows>,
+) {
+    touch_windows.changed = !touch_events.is_empty();
+    for event in touch_events.read() {
+        match event.phase {
+            TouchPhase::Started => {
+                touch_windows.windows.insert(event.id, event.window);
+            }
+            TouchPhase::Ended | TouchPhase::Canceled => {
+                touch_windows.windows.remove(&event.id);
+            }
+            TouchPhase::Moved => {}
+        }
+    }
+}
+
+/// A two finger pinch gesture, in logical window coordinates
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TouchPinch {
+    /// The window the gesture happens in
+    pub window: Entity,
+    /// The point between the fingers in the previous frame
+    pub previous_midpoint: Vec2,
+    /// The point between the fingers
+    pub midpoint: Vec2,
+    /// How much the projection scale should be multiplied by to follow the
+    /// fingers
+    pub scale_factor: f32,
+    /// How far the fingers turned counterclockwise on screen, in radians
+    pub rotation: f32,
+}
+
+/// Flips a vector in window coordinates, where y grows downwards, so angles
+/// are counterclockwise on screen
+fn y_up(v: Vec2) -> Vec2 {
+    vec2(v.x, -v.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        do_camera_movement,
+        test_utils::{input_app, mock_proj, touch},
+    };
+
+    use super::*;
+
+    #[test]
+    fn touch_drags_follow_the_finger() {
+        let mut app = input_app();
+        app.add_systems(Update, (track_touch_windows, do_camera_movement).chain());
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam::default(),
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+
+        touch(&mut app, 0, TouchPhase::Started, vec2(40., 50.));
+        app.update();
+        touch(&mut app, 0, TouchPhase::Moved, vec2(60., 40.));
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(-20., -10., 0.));
+
+        // A resting finger holds the camera still
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(-20., -10., 0.));
+
+        // A second finger turns the drag into a pinch
+        touch(&mut app, 1, TouchPhase::Started, vec2(80., 40.));
+        app.update();
+        touch(&mut app, 0, TouchPhase::Moved, vec2(50., 40.));
+        touch(&mut app, 1, TouchPhase::Moved, vec2(90., 40.));
+        app.update();
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation, vec3(-20., -10., 0.));
+        assert!(!app.world().get::<DragState>(camera).unwrap().grabbed);
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use crate::{
+    DragState, PanCam, PanCamState, PanCamWrapped, SnapState, fly_to::Flight, follow::FollowState,
+};
+
+/// Moves cameras that crossed their bounds on a wrapping axis to the other
+/// side of the world
+pub(crate) fn wrap_cameras(
+    mut query: Query<(Entity, &PanCam, &mut Transform)>,
+    mut states: Query<(&mut DragState, &mut SnapState, &mut PanCamState)>,
+    mut transitions: Query<(Option<&mut Flight>, Option<&mut FollowState>)>,
+    mut commands: Commands,
+) {
+    for (entity, pan_cam, mut transform) in &mut query {
+        let offset = wrap_offset(transform.translation.truncate(), pan_cam);
+        if offset == Vec2::ZERO {
+            continue;
+        }
+
+        transform.translation += offset.extend(0.);
+
+        // Everything that remembers where the camera was moves along, so the
+        // teleport is seamless
+        if let Ok((mut drag_state, mut snap_state, mut state)) = states.get_mut(entity) {
+            if let Some(unresisted_pos) = &mut drag_state.unresisted_pos {
+                *unresisted_pos += offset;
+            }
+            snap_state.shift(offset);
+            state.shift(offset);
+        }
+        if let Ok((flight, follow_state)) = transitions.get_mut(entity) {
+            if let Some(mut flight) = flight {
+                flight.shift(offset);
+            }
+            if let Some(mut follow_state) = follow_state {
+                follow_state.shift(offset);
+            }
+        }
+
+        commands.trigger(PanCamWrapped {
+            entity,
+            offset,
+            translation: transform.translation.truncate(),
+        });
+    }
+}
+
+/// `wrap_offset` finds how far a camera position has to move to get back
+/// within the bounds on wrapping axes.
+pub(crate) fn wrap_offset(pos: Vec2, pan_cam: &PanCam) -> Vec2 {
+    let wrap = |pos: f32, min: f32, max: f32, wrap: bool| {
+        let size = max - min;
+        if !wrap || !size.is_finite() || size <= 0. || (min..max).contains(&pos) {
+            return 0.;
+        }
+        -size * ((pos - min) / size).floor()
+    };
+
+    vec2(
+        wrap(pos.x, pan_cam.min_x, pan_cam.max_x, pan_cam.wrap_x),
+        wrap(pos.y, pan_cam.min_y, pan_cam.max_y, pan_cam.wrap_y),
+    )
+}
+
+/// `shortest_offset` finds the shortest way to cover an offset between two
+/// positions, which on wrapping axes may go the other way around the world.
+pub(crate) fn shortest_offset(offset: Vec2, pan_cam: &PanCam) -> Vec2 {
+    let wrap_size = pan_cam.wrap_size();
+    let shortest = |offset: f32, size: f32| {
+        if !size.is_finite() || size <= 0. {
+            return offset;
+        }
+        offset - size * (offset / size).round()
+    };
+
+    vec2(
+        shortest(offset.x, wrap_size.x),
+        shortest(offset.y, wrap_size.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BoundsPadding, clamp_to_safe_zone, constrain_proj_scale, test_utils::mock_proj};
+
+    use super::*;
+
+    #[test]
+    fn wrapping_moves_the_camera_by_whole_worlds() {
+        let pan_cam = PanCam {
+            min_x: -100.,
+            max_x: 100.,
+            min_y: -50.,
+            max_y: 50.,
+            wrap_x: true,
+            ..default()
+        };
+        assert_eq!(wrap_offset(vec2(99., 0.), &pan_cam), Vec2::ZERO);
+        assert_eq!(wrap_offset(vec2(101., 0.), &pan_cam), vec2(-200., 0.));
+        assert_eq!(wrap_offset(vec2(-500., 0.), &pan_cam), vec2(400., 0.));
+        // Only wrapping axes are moved
+        assert_eq!(wrap_offset(vec2(0., 80.), &pan_cam), Vec2::ZERO);
+        // The camera isn't clamped on wrapping axes
+        assert_eq!(
+            clamp_to_safe_zone(vec2(150., 80.), &pan_cam, vec2(100., 20.)),
+            vec2(150., 40.)
+        );
+    }
+
+    #[test]
+    fn wrapping_worlds_are_never_shown_twice() {
+        let window_size = vec2(100., 100.);
+        let mut proj = mock_proj(window_size);
+        proj.scale = 10.;
+        let pan_cam = PanCam {
+            min_x: -100.,
+            max_x: 100.,
+            wrap_x: true,
+            // The padding doesn't let the world repeat either
+            bounds_padding: BoundsPadding::ViewportFraction(0.5),
+            ..default()
+        };
+        constrain_proj_scale(&mut proj, &pan_cam, window_size, Quat::IDENTITY);
+        assert_eq!(proj.scale, 2.);
+    }
+
+    #[test]
+    fn wrapping_triggers_an_event() {
+        #[derive(Resource, Default)]
+        struct Wraps(Vec<PanCamWrapped>);
+
+        let mut app = App::new();
+        app.init_resource::<Wraps>()
+            .add_systems(Update, wrap_cameras)
+            .add_observer(|wrapped: On<PanCamWrapped>, mut wraps: ResMut<Wraps>| {
+                wraps.0.push(*wrapped);
+            });
+
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    min_y: -100.,
+                    max_y: 100.,
+                    wrap_y: true,
+                    ..default()
+                },
+                Transform::from_xyz(0., -120., 0.),
+            ))
+            .id();
+        app.update();
+
+        let wraps = &app.world().resource::<Wraps>().0;
+        assert_eq!(
+            wraps.as_slice(),
+            [PanCamWrapped {
+                entity: camera,
+                offset: vec2(0., 200.),
+                translation: vec2(0., 80.),
+            }]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+/// How long continuous zooming has to stop before the camera settles on a
+/// zoom step, as trackpads and gamepads don't say when a gesture ends
+const ZOOM_SETTLE_DELAY: f32 = 0.15;
+
+/// A continuous zoom in between zoom steps, see
+/// [`PanCam::zoom_steps`](crate::PanCam::zoom_steps)
+#[derive(Clone, Copy)]
+pub(crate) struct ContinuousZoom {
+    /// The elapsed real time of the last continuous zoom input
+    pub last_input: f32,
+    /// Whether the zoom is a touch pinch, which ends as soon as a finger is
+    /// lifted
+    pub touch: bool,
+    /// Whether the zoom is towards the middle of the view
+    pub centered: bool,
+}
+
+/// `step_zoom` finds the scale a camera with zoom steps goes to this frame,
+/// and whether it zooms towards the middle of the view, when the zoom steps
+/// decide it.
+///
+/// Wheel notches and key presses, counted by `steps`, move between levels.
+/// Pinching and gamepads change the scale by `scale_factor` instead, and
+/// settle on the nearest level once they stop. `zoom` describes the input of
+/// this frame, and `continuous_zoom` the continuous zoom going on.
+pub(crate) fn step_zoom(
+    levels: &[f32],
+    continuous_zoom: &mut Option<ContinuousZoom>,
+    zoom: ContinuousZoom,
+    base_scale: f32,
+    scale_factor: f32,
+    steps: i32,
+) -> Option<(f32, bool)> {
+    // Fingers resting on the screen are still pinching, even when they don't
+    // move
+    let zooming_continuously = scale_factor != 1. || zoom.touch;
+
+    let step_zoom = if steps != 0 {
+        if !zooming_continuously {
+            *continuous_zoom = None;
+        }
+        Some((
+            step_zoom_level(levels, base_scale * scale_factor, steps),
+            zoom.centered,
+        ))
+    } else if let Some(last_zoom) = *continuous_zoom
+        && !zooming_continuously
+        && (last_zoom.touch || zoom.last_input - last_zoom.last_input >= ZOOM_SETTLE_DELAY)
+    {
+        *continuous_zoom = None;
+        Some((nearest_zoom_level(levels, base_scale), last_zoom.centered))
+    } else {
+        None
+    };
+
+    if zooming_continuously {
+        *continuous_zoom = Some(zoom);
+    }
+    step_zoom
+}
+
+/// `step_zoom_level` finds the zoom level `steps` levels away from `scale`.
+/// Positive steps zoom in, towards smaller scales.
+///
+/// A scale in between levels counts as being just past the level it's zooming
+/// away from, so a single step always goes to the next level.
+fn step_zoom_level(levels: &[f32], scale: f32, steps: i32) -> f32 {
+    let mut levels = levels.to_vec();
+    levels.sort_by(f32::total_cmp);
+    let Some(last) = levels.len().checked_sub(1) else {
+        return scale;
+    };
+
+    let tolerance = scale * 0.001;
+    let index = if steps > 0 {
+        let below = levels.iter().filter(|level| **level < scale - tolerance);
+        below.count() as i32 - steps
+    } else {
+        let at_or_below = levels.iter().filter(|level| **level <= scale + tolerance);
+        at_or_below.count() as i32 - 1 - steps
+    };
+
+    levels[index.clamp(0, last as i32) as usize]
+}
+
+/// `nearest_zoom_level` finds the zoom level closest to `scale`, comparing
+/// ratios so that zooming in and out by the same factor is equally far.
+fn nearest_zoom_level(levels: &[f32], scale: f32) -> f32 {
+    levels
+        .iter()
+        .copied()
+        .min_by(|a, b| {
+            let distance = |level: f32| (level / scale).ln().abs();
+            distance(*a).total_cmp(&distance(*b))
+        })
+        .unwrap_or(scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        input::{gestures::PinchGesture, touch::TouchPhase},
+        prelude::*,
+    };
+
+    use crate::{
+        PanCam, do_camera_zoom,
+        test_utils::{input_app, mock_proj, scale, touch},
+        touch,
+    };
+
+    use super::*;
+
+    #[test]
+    fn zoom_steps_move_to_the_next_level() {
+        let levels = [4., 0.25, 1., 0.5, 2.];
+        assert_eq!(step_zoom_level(&levels, 1., 1), 0.5);
+        assert_eq!(step_zoom_level(&levels, 1., -2), 4.);
+        assert_eq!(step_zoom_level(&levels, 0.7, 1), 0.5);
+        assert_eq!(step_zoom_level(&levels, 0.7, -1), 1.);
+        assert_eq!(step_zoom_level(&levels, 0.25, 1), 0.25);
+        assert_eq!(step_zoom_level(&levels, 100., 1), 4.);
+        assert_eq!(step_zoom_level(&[], 3., 1), 3.);
+    }
+
+    #[test]
+    fn zoom_settles_on_the_nearest_level() {
+        let levels = [0.25, 0.5, 1., 2., 4.];
+        assert_eq!(nearest_zoom_level(&levels, 0.7), 0.5);
+        assert_eq!(nearest_zoom_level(&levels, 0.72), 1.);
+        assert_eq!(nearest_zoom_level(&levels, 10.), 4.);
+    }
+
+    #[test]
+    fn touch_pinch_settles_on_a_zoom_step_when_lifted() {
+        let mut app = input_app();
+        app.add_systems(Update, (touch::track_touch_windows, do_camera_zoom).chain());
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    zoom_steps: Some(vec![0.25, 1., 4.]),
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+
+        touch(&mut app, 0, TouchPhase::Started, vec2(40., 50.));
+        touch(&mut app, 1, TouchPhase::Started, vec2(60., 50.));
+        app.update();
+        touch(&mut app, 0, TouchPhase::Moved, vec2(35., 50.));
+        touch(&mut app, 1, TouchPhase::Moved, vec2(65., 50.));
+        app.update();
+        assert!((scale(&app, camera) - 2. / 3.).abs() < 1e-5);
+
+        // Resting fingers keep the zoom in between steps
+        app.update();
+        app.update();
+        assert!((scale(&app, camera) - 2. / 3.).abs() < 1e-5);
+
+        touch(&mut app, 0, TouchPhase::Ended, vec2(35., 50.));
+        touch(&mut app, 1, TouchPhase::Ended, vec2(65., 50.));
+        app.update();
+        assert_eq!(scale(&app, camera), 1.);
+    }
+
+    #[test]
+    fn trackpad_pinch_settles_on_a_zoom_step_after_a_pause() {
+        let mut app = input_app();
+        app.add_systems(Update, do_camera_zoom);
+        let camera = app
+            .world_mut()
+            .spawn((
+                PanCam {
+                    zoom_steps: Some(vec![0.25, 1., 4.]),
+                    ..default()
+                },
+                Projection::Orthographic(mock_proj(vec2(100., 100.))),
+            ))
+            .id();
+
+        app.world_mut().write_message(PinchGesture(0.2));
+        app.update();
+        assert_eq!(scale(&app, camera), 0.8);
+
+        // Pinch events don't arrive every frame, so a short gap isn't the end
+        app.update();
+        assert_eq!(scale(&app, camera), 0.8);
+        app.update();
+        assert_eq!(scale(&app, camera), 1.);
+    }
+}